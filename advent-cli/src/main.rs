@@ -1,5 +1,8 @@
 use std::{
     io::{self, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::ExitCode,
     thread::sleep,
     time::Duration,
 };
@@ -7,22 +10,100 @@ use std::{
 use chrono::prelude::*;
 
 use advent::{
-    fetch::{get_all_inputs, get_or_fetch_input},
-    input_store::{set_cookie, Selector},
+    answer::{format_wait, Verdict},
+    article::{render_page, Style},
+    bench, calendar,
+    config::{self, Config},
+    cooldown,
+    error::{advent_error, AdventError},
+    examples::{self, Example, Written},
+    fetch::{Site, LEADERBOARD_TTL},
+    input_store::{self, set_cookie, Selector, DEFAULT_PROFILE},
+    inspect, json,
+    leaderboard::Sort,
+    ledger::{Entry, Ledger},
+    repo::{self, Solution},
+    run,
+    scaffold::{self, Template},
+    schedule::{self, SystemClock},
+    source::{Cached, DirectorySource, InputSource},
+    status::YearStatus,
+    sync::{self, Outcome, Summary, Sync},
+    verify::DayCheck,
+    watch::{self, Panel, Snapshot},
 };
-use clap::{command, Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
-use anyhow::Result;
+use anyhow::{Error, Result};
 use humantime::format_duration;
 use rpassword::prompt_password;
+use serde::Serialize;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// wait out any cooldown the site asked for instead of failing
+    #[clap(long, global = true, action, default_value_t = false, value_parser)]
+    wait_cooldown: bool,
+
+    /// talk to a stand-in for adventofcode.com, defaults to $AOC_BASE_URL
+    #[clap(long, global = true)]
+    base_url: Option<String>,
+
+    /// read inputs from a directory laid out like $AOC_CONFIG/input instead of the site
+    #[clap(long, global = true)]
+    mirror: Option<PathBuf>,
+
+    /// use a named account's cookie and inputs, defaults to $AOC_PROFILE
+    #[clap(long, global = true)]
+    profile: Option<String>,
+
+    /// print get, show, status, submit, run --submit and path results as json on stdout
+    #[clap(long, global = true, action, default_value_t = false, value_parser)]
+    json: bool,
+
     #[command(subcommand)]
     command: Command,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SortBy {
+    Score,
+    Stars,
+    Name,
+    Recent,
+}
+
+impl From<SortBy> for Sort {
+    fn from(sort: SortBy) -> Self {
+        match sort {
+            SortBy::Score => Sort::LocalScore,
+            SortBy::Stars => Sort::Stars,
+            SortBy::Name => Sort::Name,
+            SortBy::Recent => Sort::LastStar,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TemplateName {
+    Plain,
+    Grid,
+    Vm,
+    Graph,
+}
+
+impl From<TemplateName> for Template {
+    fn from(template: TemplateName) -> Self {
+        match template {
+            TemplateName::Plain => Template::Plain,
+            TemplateName::Grid => Template::Grid,
+            TemplateName::Vm => Template::Vm,
+            TemplateName::Graph => Template::Graph,
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Get a specific day's input
@@ -33,11 +114,33 @@ enum Command {
         /// waits until the puzzle is unlocked and then runs
         #[clap(short, long, action, default_value_t = false, value_parser)]
         wait: bool,
+
+        /// fetch again even if it's cached, showing what changed
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        force: bool,
     },
 
     /// Get the inputs for all of a year
     GetYear { year: usize },
 
+    /// List the days of a year's event, one per line
+    Days { year: usize },
+
+    /// Fetch every missing input across event years, picking up where the last sync stopped
+    Sync {
+        /// eg. `2015..2025` or `2022`, defaults to every event so far
+        #[clap(long, value_parser = sync::parse_years)]
+        years: Option<RangeInclusive<usize>>,
+
+        /// fetch puzzle descriptions too
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        puzzles: bool,
+
+        /// how long to wait between requests, defaults to config.toml's throttle.sync or 2s
+        #[clap(long)]
+        throttle: Option<humantime::Duration>,
+    },
+
     /// Show the input for a specific day
     Show {
         year: usize,
@@ -48,93 +151,1441 @@ enum Command {
         wait: bool,
     },
 
+    /// Show the puzzle description for a specific day
+    Read {
+        year: usize,
+        day: usize,
+
+        /// render as markdown instead of styled terminal text
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        markdown: bool,
+
+        /// refetch the page even if it's cached
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        force: bool,
+    },
+
+    /// List the example blocks in a day's puzzle, optionally saving them as test fixtures
+    Examples {
+        year: usize,
+        day: usize,
+
+        /// blocks to save under the day crate's fixtures directory, eg. `1,3`
+        #[clap(short, long, value_delimiter = ',')]
+        pick: Vec<usize>,
+
+        /// save every block
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        all: bool,
+    },
+
+    /// Describe a day's input and suggest a helper to parse it with
+    Inspect {
+        year: usize,
+        day: usize,
+
+        /// print a `type Input` and `fn parse` for the best suggestion instead
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        stub: bool,
+    },
+
+    /// Show a private leaderboard
+    Leaderboard {
+        year: usize,
+        id: u64,
+
+        #[clap(short, long, value_enum, default_value_t = SortBy::Score)]
+        sort: SortBy,
+
+        /// show part 1 / part 2 completion times for a single day
+        #[clap(short, long)]
+        day: Option<usize>,
+    },
+
+    /// Submit an answer for a specific day's part
+    Submit {
+        year: usize,
+        day: usize,
+
+        #[clap(value_parser = clap::value_parser!(u16).range(1..=2))]
+        part: u16,
+
+        answer: String,
+
+        /// submit even if the answer ledger would refuse it
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        force: bool,
+    },
+
+    /// Start a new day's crate from a template
+    New {
+        year: usize,
+        day: usize,
+
+        #[clap(short, long, value_enum, default_value_t = TemplateName::Plain)]
+        template: TemplateName,
+
+        /// fetch the input and save the puzzle's examples as fixtures too
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        fetch: bool,
+
+        /// with --fetch, wait for the day to unlock first
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        wait: bool,
+
+        /// don't add the day to advent-rs.code-workspace
+        #[clap(long, action, default_value_t = false, value_parser)]
+        no_link: bool,
+    },
+
+    /// Build and run a day's solution, showing its answers and how long it took
+    Run {
+        year: usize,
+        day: usize,
+
+        /// build with optimizations
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        release: bool,
+
+        /// submit the answer the run printed for this part
+        #[clap(short, long, value_parser = clap::value_parser!(u16).range(1..=2))]
+        submit: Option<u16>,
+
+        /// submit even if the answer ledger would refuse it
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        force: bool,
+    },
+
+    /// Sleep until each remaining day of an event unlocks, then fetch its input and puzzle
+    Await {
+        /// defaults to the event that's running or next to start
+        year: Option<usize>,
+
+        /// run through `sh -c` from the repository after each day is fetched,
+        /// eg. "just prebuild-day {{year}} {{day}}", defaults to config.toml's hooks.await
+        #[clap(long)]
+        hook: Option<String>,
+    },
+
+    /// Rebuild a day whenever its source or fixtures change, then test it and run it on the examples and the input
+    Watch { year: usize, day: usize },
+
+    /// Time each day's release build and compare it with the last time
+    Bench {
+        /// defaults to every year with a directory here
+        year: Option<usize>,
+        /// defaults to every day of the year with a solution
+        day: Option<usize>,
+
+        /// timed runs per day, after one to warm up
+        #[clap(short, long, default_value_t = 5)]
+        runs: usize,
+
+        /// flag days more than this many percent slower than last time
+        #[clap(short, long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+
+    /// Run every solved day and check its answers against the ones the site accepted
+    Verify {
+        /// defaults to every year with a directory here
+        year: Option<usize>,
+
+        /// refetch the puzzle page of days missing a known answer, to pick it up
+        #[clap(short, long, action, default_value_t = false, value_parser)]
+        fetch: bool,
+
+        /// how long to wait between requests, defaults to config.toml's throttle.verify or 2s
+        #[clap(long)]
+        throttle: Option<humantime::Duration>,
+    },
+
     /// Set your adventofcode.com cookie
     SetCookie { cookie: Option<String> },
+
+    /// Show stars from the site next to which days have solutions in this repository
+    Status {
+        /// defaults to every event so far
+        year: Option<usize>,
+    },
+
+    /// Check the stored cookie against the site and show who it logs in as
+    Whoami,
+
+    /// List the profiles with a cookie or cached inputs
+    Profiles,
+
+    /// Show or change the settings in $AOC_CONFIG/config.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Show where a day's input is cached, or with no day the profile's directories
+    Path {
+        #[clap(requires = "day")]
+        year: Option<usize>,
+        day: Option<usize>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigAction {
+    /// Show a setting's value, from the environment, config.toml or the default
+    Get { key: String },
+
+    /// Save a setting to config.toml, or remove it when there's no value
+    Set { key: String, value: Option<String> },
+
+    /// Show every setting and where its value comes from
+    List,
+}
+
+/// the global flags the commands share
+struct Opts<'a> {
+    base_url: Option<&'a str>,
+    mirror: Option<&'a Path>,
+    wait_cooldown: bool,
+    json: bool,
+}
+
+fn repo_root(what: &str) -> Result<PathBuf> {
+    repo::root()
+        .ok_or_else(|| Error::msg(format!("not in an advent-rs checkout, can't find {what}")))
+}
+
+fn warn_uncached(uncached: usize) {
+    if uncached > 0 {
+        eprintln!("skipped {uncached} days without a cached input, `advent sync` fetches them");
+    }
+}
+
+// only built by the commands that talk to the site, so a bad cookie can't stop `set-cookie` replacing it
+fn connect(base_url: Option<&str>) -> Result<Site> {
+    match base_url {
+        Some(base_url) => Site::from_store(base_url),
+        None => Site::from_env(),
+    }
+}
+
+fn cached_inputs<'a>(mirror: Option<&Path>, site: &'a Site) -> Cached<Box<dyn InputSource + 'a>> {
+    match mirror {
+        Some(dir) => Cached::new(Box::new(DirectorySource::new(dir))),
+        None => Cached::new(Box::new(site)),
+    }
+}
+
+// progress goes to stderr, keeping stdout for results (and `--json`)
+fn print_countdown(selector: &Selector, remaining: Duration) {
+    eprint!(
+        "  {}-{:0>2} unlocks in {}              \r",
+        selector.year(),
+        selector.day(),
+        format_duration(Duration::from_secs(remaining.as_secs()))
+    );
+    let _ = io::stderr().flush();
+}
+
+// the day just unlocked, so the site might not have it ready yet
+fn fetch_unlocked<S: InputSource>(
+    inputs: &Cached<S>,
+    selector: &Selector,
+    force: bool,
+) -> Result<String> {
+    schedule::retry(
+        &SystemClock,
+        10,
+        MAX_BACKOFF,
+        || polite(true, || inputs.get(selector, force)),
+        |attempt, err| eprintln!("attempt {attempt} failed: {err}"),
+    )
+}
+
+fn wait_for<S: InputSource>(
+    inputs: &Cached<S>,
+    selector: &Selector,
+    force: bool,
+) -> Result<String> {
+    let unlock = calendar::unlocks_at(selector.year(), selector.day());
+    schedule::wait_until(&SystemClock, unlock, |remaining| {
+        print_countdown(selector, remaining)
+    });
+    eprintln!("{} ... fetching", Local::now());
+    fetch_unlocked(inputs, selector, force)
+}
+
+fn print_example(example: &Example) {
+    let lines: Vec<&str> = example.text.lines().collect();
+    let answers = match example.answers.is_empty() {
+        true => "answer ?".to_string(),
+        false => example
+            .answers
+            .iter()
+            .map(|(part, answer)| format!("part {part} answer {answer}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    println!(
+        "block {} (part {}, {} lines, {answers})",
+        example.block,
+        example.part,
+        lines.len()
+    );
+    for line in lines.iter().take(PREVIEW_LINES) {
+        println!("  | {line}");
+    }
+    if lines.len() > PREVIEW_LINES {
+        println!("  | ...");
+    }
+    println!();
+}
+
+const PREVIEW_LINES: usize = 6;
+
+fn save_example(dir: &Path, example: &Example) -> Result<()> {
+    let fixture = |number: usize| dir.join(format!("example_{number:02}.txt"));
+    match examples::write_fixture(dir, example)? {
+        Written::New(number) => eprintln!(
+            "saved block {} as {}",
+            example.block,
+            fixture(number).display()
+        ),
+        Written::Updated(number) => eprintln!(
+            "block {} is already {}, added its new answers",
+            example.block,
+            fixture(number).display()
+        ),
+        Written::Unchanged(number) => eprintln!(
+            "block {} is already {}",
+            example.block,
+            fixture(number).display()
+        ),
+    }
+    Ok(())
+}
+
+// failures are listed at the end, cached and locked days are only counted
+fn print_outcome(selector: &Selector, outcome: &Outcome) {
+    if *outcome == Outcome::Fetched {
+        eprintln!("{}-{:0>2}: {outcome}", selector.year(), selector.day());
+    }
+}
+
+fn print_failures(summary: &Summary) {
+    if summary.failed.is_empty() {
+        return;
+    }
+    eprintln!("failed:");
+    for (selector, reason) in &summary.failed {
+        eprintln!("  {}-{:0>2}: {reason}", selector.year(), selector.day());
+    }
+}
+
+fn warn_problems(selector: &Selector) -> Result<()> {
+    for problem in input_store::check_puzzle_input(selector)? {
+        eprintln!(
+            "warning: {}-{:0>2}: {problem}, refetch it with `advent get --force`",
+            selector.year(),
+            selector.day()
+        );
+    }
+    Ok(())
 }
 
-fn wait_for(selector: &Selector, force: bool) -> Result<String> {
-    let until = Utc
-        .with_ymd_and_hms(selector.year as i32, 12, (selector.day) as u32, 5, 0, 0)
-        .single()
-        .unwrap()
-        .naive_utc();
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
 
+// days in `years` with at least one part solved here
+fn solved_days(root: &Path, years: Vec<usize>) -> Vec<Selector> {
+    years
+        .into_iter()
+        .flat_map(Selector::days)
+        .filter(|s| {
+            matches!(
+                repo::solution(root, s.year(), s.day()),
+                Solution::Done | Solution::Partial
+            )
+        })
+        .collect()
+}
+
+/// builds, tests and runs a day once, for `advent watch`
+fn watch_panel(root: &Path, selector: &Selector, manifest: &Path, changed: &[PathBuf]) -> Panel {
+    let (year, day) = (selector.year(), selector.day());
+    let mut title = format!(
+        "{year}-{day:0>2} rebuilt at {}",
+        Local::now().format("%H:%M:%S")
+    );
+    if let Some(first) = changed.first() {
+        let name = first
+            .strip_prefix(repo::day_dir(root, year, day))
+            .unwrap_or(first);
+        title.push_str(&format!(", {} changed", name.display()));
+        if changed.len() > 1 {
+            title.push_str(&format!(" and {} more", changed.len() - 1));
+        }
+    }
+
+    let mut panel = Panel {
+        title,
+        build: Ok(()),
+        tests: None,
+        examples: vec![],
+        input: None,
+    };
+
+    let binary = match run::build_quiet(manifest, false) {
+        Ok(binary) => binary,
+        Err(err) => {
+            panel.build = Err(format!("{err:#}"));
+            return panel;
+        }
+    };
+    panel.tests = Some(run::test(manifest).map_err(|err| format!("{err:#}")));
+
+    let outcome = |ran: Result<run::Run>, expected: Vec<(u16, String)>| match ran {
+        Ok(ran) => watch::Outcome::Answers {
+            answers: ran.answers,
+            expected,
+            elapsed: ran.elapsed,
+        },
+        Err(err) => watch::Outcome::Failed(format!("{err:#}")),
+    };
+
+    match examples::read_fixtures(&repo::fixtures_dir(root, year, day)) {
+        Ok(fixtures) if !fixtures.is_empty() && !repo::uses_runner(root, year, day) => {
+            panel.examples.push((
+                0,
+                watch::Outcome::Skipped(
+                    "skipped, the day doesn't use advent::runner to read them".to_string(),
+                ),
+            ))
+        }
+        Ok(fixtures) => {
+            for fixture in fixtures {
+                let ran = run::run_example(&binary, fixture.number);
+                panel
+                    .examples
+                    .push((fixture.number, outcome(ran, fixture.answers)));
+            }
+        }
+        Err(err) => panel
+            .examples
+            .push((0, watch::Outcome::Failed(format!("{err:#}")))),
+    }
+
+    panel.input = Some(match selector.exists() {
+        true => outcome(run::run_quiet(&binary), vec![]),
+        false => watch::Outcome::Failed("no cached input".to_string()),
+    });
+    panel
+}
+
+// runs `f`, sleeping through and retrying any cooldown it hits if `wait` is set
+fn polite<T>(wait: bool, f: impl Fn() -> Result<T>) -> Result<T> {
     loop {
-        let now = Local::now();
-        let now_utc = now.naive_utc();
-        if now_utc < until {
-            let remaining = until - now_utc;
-            print!(
-                "  {now} ... waiting. {}              \r",
-                format_duration(remaining.to_std()?)
+        match f() {
+            Err(err) if wait => match cooldown::throttled(&err) {
+                Some(throttled) => {
+                    eprintln!("{throttled}, waiting");
+                    sleep(throttled.remaining + Duration::from_secs(1));
+                }
+                None => return Err(err),
+            },
+            result => return result,
+        }
+    }
+}
+
+// checks the answer against the ledger, submits it and records the verdict
+fn submit(
+    site: &Site,
+    wait: bool,
+    selector: &Selector,
+    part: u16,
+    answer: &str,
+    force: bool,
+) -> Result<Verdict> {
+    let (year, day) = (selector.year(), selector.day());
+    let answer = answer.trim();
+
+    if !calendar::parts(year, day).contains(&part) {
+        return Err(Error::msg(format!(
+            "{year}-{day:0>2} only has one part, its second star comes from finishing every other day"
+        )));
+    }
+
+    if !force {
+        Ledger::load(selector)?
+            .check(part, answer)
+            .map_err(|refusal| {
+                Error::msg(format!(
+                    "refusing to submit: {refusal} (use --force to submit anyway)"
+                ))
+            })?;
+    }
+
+    let verdict = polite(wait, || site.submit_answer(selector, part, answer))?;
+    Ledger::record(selector, &Entry::new(part, answer, verdict.clone()))?;
+    Ok(verdict)
+}
+
+// the global flags beat everything config.toml and the environment say
+fn setting(
+    config: &Config,
+    profile: Option<&str>,
+    base_url: Option<&str>,
+    key: &str,
+) -> Result<Option<(String, config::Source)>> {
+    let flag = match key {
+        "profile" => profile.map(|p| (p, "--profile")),
+        "base_url" => base_url.map(|u| (u, "--base-url")),
+        _ => None,
+    };
+    match flag {
+        Some((value, flag)) => Ok(Some((value.to_string(), config::Source::Flag(flag)))),
+        None => config.effective(key),
+    }
+}
+
+// config.toml is only read here, leniently, so these still work to fix a broken one
+fn configure(action: ConfigAction, profile: Option<&str>, base_url: Option<&str>) -> Result<()> {
+    let (mut config, broken) = Config::load_lenient()?;
+    match action {
+        ConfigAction::Get { key } => {
+            if let Some(err) = &broken {
+                eprintln!("warning: ignoring config.toml, {err}");
+            }
+            match setting(&config, profile, base_url, &key)? {
+                Some((value, _)) => println!("{value}"),
+                None => return Err(Error::msg(format!("{key} isn't set"))),
+            }
+        }
+        ConfigAction::Set { key, value } => {
+            config.set(&key, value.as_deref())?;
+            if let Some(err) = &broken {
+                let backup = Config::set_aside()?;
+                eprintln!(
+                    "warning: moved the broken config.toml to {}, {err}",
+                    backup.display()
+                );
+            }
+            config.save()?;
+            if let Some((value, source)) = config.lookup(&key)? {
+                if source != config::Source::File {
+                    eprintln!("warning: {source} is set, so {key} is still {value}");
+                }
+            }
+        }
+        ConfigAction::List => {
+            if let Some(err) = &broken {
+                eprintln!("warning: ignoring config.toml, {err}");
+            }
+            for key in config::KEYS {
+                let shown = match setting(&config, profile, base_url, key.name)? {
+                    Some((value, source)) => format!("{value:?} ({source})"),
+                    None => "unset".to_string(),
+                };
+                println!("{:<16} {shown}", key.name);
+                println!("{:<16} {}", "", key.about);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+// what to do about the errors we know how to explain
+fn hint(err: &Error) -> Option<String> {
+    let hint = match advent_error(err)? {
+        AdventError::MissingConfig => {
+            "set $AOC_CONFIG to the directory inputs and cookies should live in".to_string()
+        }
+        AdventError::MissingCookie { profile, .. } if profile == DEFAULT_PROFILE => {
+            "run `advent set-cookie` with your adventofcode.com session cookie".to_string()
+        }
+        AdventError::MissingCookie { profile, .. } => {
+            format!(
+                "run `advent --profile {profile} set-cookie` with that account's session cookie"
+            )
+        }
+        AdventError::InvalidCookie => {
+            "the stored cookie has characters a header can't hold, run `advent set-cookie` again"
+                .to_string()
+        }
+        AdventError::MissingInput { year, day, .. } => {
+            format!("fetch it first with `advent get {year} {day}`")
+        }
+        AdventError::BrokenInput { year, day, .. } => {
+            format!("refetch it with `advent get {year} {day} --force`")
+        }
+        AdventError::NoSuchDay { .. } => return None,
+        AdventError::NotUnlocked { .. } => "pass --wait to wait for it to unlock".to_string(),
+        AdventError::Http { status: 404, .. } => {
+            "check the year and day, or the leaderboard id".to_string()
+        }
+        AdventError::Http { status, .. } if *status >= 500 => {
+            "the site is having trouble, try again in a bit".to_string()
+        }
+        AdventError::Http { .. } => return None,
+        AdventError::LoggedOut => {
+            "your cookie expired, run `advent set-cookie` with a fresh one".to_string()
+        }
+        AdventError::Io { .. } => "check that $AOC_CONFIG exists and is writable".to_string(),
+        AdventError::UnknownConfigKey { .. } => {
+            "`advent config list` shows every setting".to_string()
+        }
+        AdventError::BadConfig { path, .. } => {
+            format!(
+                "fix or remove {}, or `advent config set` starts a new one",
+                path.display()
+            )
+        }
+    };
+    Some(hint)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let json = cli.json;
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            let hint = hint(&err);
+            if let Some(hint) = &hint {
+                eprintln!("hint: {hint}");
+            }
+            if json {
+                let error = serde_json::json!({ "error": format!("{err:#}"), "hint": hint });
+                let _ = print_json(&error);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let opts = Opts {
+        base_url: cli.base_url.as_deref(),
+        mirror: cli.mirror.as_deref(),
+        wait_cooldown: cli.wait_cooldown,
+        json: cli.json,
+    };
+
+    let command = match cli.command {
+        Command::Config { action } => {
+            return configure(action, cli.profile.as_deref(), opts.base_url)
+        }
+        command => command,
+    };
+
+    // resolved once, so a bad name or config.toml fails here rather than on first use
+    let profile = input_store::init_profile(cli.profile.as_deref())?;
+
+    match command {
+        Command::Get {
+            year,
+            day,
+            wait,
+            force,
+        } => cmd_get(&opts, year, day, wait, force),
+        Command::GetYear { year } => cmd_get_year(&opts, year),
+        Command::Days { year } => cmd_days(year),
+        Command::Sync {
+            years,
+            puzzles,
+            throttle,
+        } => cmd_sync(&opts, years, puzzles, throttle),
+        Command::Show { year, day, wait } => cmd_show(&opts, year, day, wait),
+        Command::Read {
+            year,
+            day,
+            markdown,
+            force,
+        } => cmd_read(&opts, year, day, markdown, force),
+        Command::Inspect { year, day, stub } => cmd_inspect(&opts, year, day, stub),
+        Command::Examples {
+            year,
+            day,
+            pick,
+            all,
+        } => cmd_examples(&opts, year, day, pick, all),
+        Command::Leaderboard {
+            year,
+            id,
+            sort,
+            day,
+        } => cmd_leaderboard(&opts, year, id, sort, day),
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            force,
+        } => cmd_submit(&opts, year, day, part, answer, force),
+        Command::New {
+            year,
+            day,
+            template,
+            fetch,
+            wait,
+            no_link,
+        } => cmd_new(&opts, year, day, template, fetch, wait, no_link),
+        Command::Run {
+            year,
+            day,
+            release,
+            submit: part,
+            force,
+        } => cmd_run(&opts, year, day, release, part, force),
+        Command::Await { year, hook } => cmd_await(&opts, year, hook),
+        Command::Watch { year, day } => cmd_watch(&opts, year, day),
+        Command::Bench {
+            year,
+            day,
+            runs,
+            threshold,
+        } => cmd_bench(year, day, runs, threshold),
+        Command::Verify {
+            year,
+            fetch,
+            throttle,
+        } => cmd_verify(&opts, year, fetch, throttle),
+        Command::SetCookie { cookie } => cmd_set_cookie(cookie),
+        Command::Status { year } => cmd_status(&opts, year),
+        Command::Whoami => cmd_whoami(&opts, &profile),
+        Command::Config { .. } => unreachable!("configure handles it"),
+        Command::Path { year, day } => cmd_path(&opts, year, day),
+        Command::Profiles => cmd_profiles(),
+    }
+}
+
+fn cmd_get(opts: &Opts, year: usize, day: usize, wait: bool, force: bool) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let mut kept = None;
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
+
+    if wait {
+        wait_for(&inputs, &selector, force)?;
+    } else if force {
+        let refetched = polite(opts.wait_cooldown, || inputs.refetch(&selector))?;
+        if let Some((previous, diff)) = refetched.changed {
+            eprint!("{diff}");
+            eprintln!(
+                "warning: {year}-{day:0>2} changed, the previous input was kept as {}",
+                previous.display()
             );
-            io::stdout().flush()?;
-            sleep(Duration::from_millis(100));
-        } else {
-            println!("{now} ... fetching");
-            break;
+            kept = Some(previous);
+        }
+    } else {
+        polite(opts.wait_cooldown, || inputs.get(&selector, false))?;
+    }
+
+    warn_problems(&selector)?;
+    eprintln!("success: fetched {year}-{day:0>2}");
+    if opts.json {
+        let mut input = json::DayInput::load(&selector)?;
+        input.previous = kept;
+        print_json(&input)?;
+    }
+
+    Ok(())
+}
+
+fn cmd_get_year(opts: &Opts, year: usize) -> Result<()> {
+    // every event has a day 1, so this only checks the year
+    calendar::validate(year, 1)?;
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
+    let sync = Sync {
+        inputs: &inputs,
+        puzzles: None,
+        throttle: Config::load()?.throttle("throttle.sync")?,
+    };
+    let summary = polite(opts.wait_cooldown, || sync.run(year..=year, print_outcome))?;
+    print_failures(&summary);
+    eprintln!("year {year}: {summary}");
+
+    Ok(())
+}
+
+fn cmd_days(year: usize) -> Result<()> {
+    calendar::validate(year, 1)?;
+    for day in calendar::days(year) {
+        println!("{day}");
+    }
+
+    Ok(())
+}
+
+fn cmd_sync(
+    opts: &Opts,
+    years: Option<RangeInclusive<usize>>,
+    puzzles: bool,
+    throttle: Option<humantime::Duration>,
+) -> Result<()> {
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
+    let sync = Sync {
+        inputs: &inputs,
+        puzzles: puzzles.then_some(&site),
+        throttle: match throttle {
+            Some(throttle) => throttle.into(),
+            None => Config::load()?.throttle("throttle.sync")?,
+        },
+    };
+    let years = years.unwrap_or_else(calendar::event_years);
+    let summary = polite(opts.wait_cooldown, || {
+        sync.run(years.clone(), print_outcome)
+    })?;
+    print_failures(&summary);
+    eprintln!("{summary}");
+
+    Ok(())
+}
+
+fn cmd_show(opts: &Opts, year: usize, day: usize, wait: bool) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
+
+    let value = if wait {
+        wait_for(&inputs, &selector, false)?
+    } else {
+        polite(opts.wait_cooldown, || inputs.get(&selector, false))?
+    };
+
+    warn_problems(&selector)?;
+    if opts.json {
+        let mut input = json::DayInput::load(&selector)?;
+        input.input = Some(value);
+        print_json(&input)?;
+    } else {
+        println!("{value}");
+    }
+
+    Ok(())
+}
+
+fn cmd_read(opts: &Opts, year: usize, day: usize, markdown: bool, force: bool) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let site = connect(opts.base_url)?;
+    let page = polite(opts.wait_cooldown, || {
+        site.get_or_fetch_puzzle(&selector, force)
+    })?;
+    let style = if markdown {
+        Style::Markdown
+    } else {
+        Style::Ansi
+    };
+
+    println!("{}", render_page(&page, style));
+
+    Ok(())
+}
+
+fn cmd_inspect(opts: &Opts, year: usize, day: usize, stub: bool) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
+    let input = polite(opts.wait_cooldown, || inputs.get(&selector, false))?;
+    let report = inspect::inspect(&input);
+    let suggestions = report.suggest();
+
+    if stub {
+        let best = suggestions.first().ok_or_else(|| {
+            Error::msg(format!(
+                "{year}-{day:0>2} doesn't fit any helper, it'll need parsing by hand"
+            ))
+        })?;
+        print!("{}", report.stub(best.helper));
+        return Ok(());
+    }
+
+    println!("{year}-{day:0>2}: {report}");
+    if suggestions.is_empty() {
+        println!("no helper fits, it'll need parsing by hand");
+    }
+    for suggestion in suggestions.iter() {
+        println!("try {}: {}", suggestion.helper, suggestion.reason);
+    }
+
+    Ok(())
+}
+
+fn cmd_examples(opts: &Opts, year: usize, day: usize, pick: Vec<usize>, all: bool) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let site = connect(opts.base_url)?;
+    let page = polite(opts.wait_cooldown, || {
+        site.get_or_fetch_puzzle(&selector, false)
+    })?;
+    let examples = examples::extract(&page);
+
+    if pick.is_empty() && !all {
+        for example in &examples {
+            print_example(example);
+        }
+        return Ok(());
+    }
+
+    let root = repo_root("the day")?;
+    let dir = repo::fixtures_dir(&root, year, day);
+
+    for block in pick.iter() {
+        if !examples.iter().any(|e| e.block == *block) {
+            return Err(Error::msg(format!("there is no block {block}")));
+        }
+    }
+
+    for example in examples.iter().filter(|e| all || pick.contains(&e.block)) {
+        save_example(&dir, example)?;
+    }
+
+    Ok(())
+}
+
+fn cmd_leaderboard(
+    opts: &Opts,
+    year: usize,
+    id: u64,
+    sort: SortBy,
+    day: Option<usize>,
+) -> Result<()> {
+    if let Some(day) = day {
+        calendar::validate(year, day)?;
+    }
+    let site = connect(opts.base_url)?;
+    let (leaderboard, age) = polite(opts.wait_cooldown, || {
+        site.get_or_fetch_leaderboard(year, id)
+    })?;
+
+    match day {
+        Some(day) => print!("{}", leaderboard.render_day(sort.into(), day)),
+        None => print!(
+            "{}",
+            leaderboard.render(sort.into(), calendar::days_in(year).unwrap_or(25))
+        ),
+    }
+
+    if !age.is_zero() {
+        let refresh = LEADERBOARD_TTL.saturating_sub(age);
+        eprintln!(
+            "cached {} ago, can refresh in {}",
+            format_wait(age),
+            format_wait(refresh)
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_submit(
+    opts: &Opts,
+    year: usize,
+    day: usize,
+    part: u16,
+    answer: String,
+    force: bool,
+) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let site = connect(opts.base_url)?;
+    let verdict = submit(&site, opts.wait_cooldown, &selector, part, &answer, force)?;
+    if opts.json {
+        print_json(&json::Submission::new(
+            &selector,
+            part,
+            answer.trim(),
+            &verdict,
+        ))?;
+    } else {
+        println!("{year}-{day:0>2} part {part}: {verdict}");
+    }
+
+    Ok(())
+}
+
+fn cmd_new(
+    opts: &Opts,
+    year: usize,
+    day: usize,
+    template: TemplateName,
+    fetch: bool,
+    wait: bool,
+    no_link: bool,
+) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let root = repo_root("the templates")?;
+    let template = Template::from(template);
+    let config = Config::load()?;
+
+    let templates = config
+        .template_dir()
+        .unwrap_or_else(|| scaffold::template_dir(&root));
+    let dir = scaffold::new_day(&root, &templates, year, day, template)?;
+    eprintln!("created {} from the {template} template", dir.display());
+
+    let workspace = root.join(scaffold::WORKSPACE);
+    let manifest = format!("./{year}/day{day:02}/Cargo.toml");
+    if !no_link {
+        if !workspace.exists() {
+            eprintln!(
+                "no {}, not linking it for rust-analyzer",
+                scaffold::WORKSPACE
+            );
+        } else if scaffold::link_project(&workspace, &manifest)? {
+            eprintln!("linked {manifest} in {}", scaffold::WORKSPACE);
+        }
+    }
+
+    if fetch {
+        let site = connect(opts.base_url)?;
+        let inputs = cached_inputs(opts.mirror, &site);
+        match wait {
+            true => wait_for(&inputs, &selector, false)?,
+            false => polite(opts.wait_cooldown, || inputs.get(&selector, false))?,
+        };
+        warn_problems(&selector)?;
+
+        let page = polite(opts.wait_cooldown, || {
+            site.get_or_fetch_puzzle(&selector, false)
+        })?;
+        let fixtures = repo::fixtures_dir(&root, year, day);
+        for example in examples::extract(&page).iter() {
+            save_example(&fixtures, example)?;
+        }
+    }
+
+    if let Some(hook) = &config.hooks.after_new {
+        schedule::run_hook(hook, &selector, &root)?;
+    }
+
+    Ok(())
+}
+
+fn cmd_run(
+    opts: &Opts,
+    year: usize,
+    day: usize,
+    release: bool,
+    part: Option<u16>,
+    force: bool,
+) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let root = repo_root("the day")?;
+    let manifest = run::manifest(&root, year, day)?;
+
+    // the day reads its input from the cache, so make sure it's there
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
+    polite(opts.wait_cooldown, || inputs.get(&selector, false))?;
+    warn_problems(&selector)?;
+
+    let binary = run::build(&manifest, release)?;
+    let ran = run::run(&binary)?;
+    // with --json only the verdict goes to stdout
+    match opts.json {
+        true => eprint!("{}", ran.stdout),
+        false => print!("{}", ran.stdout),
+    }
+
+    let build = if release { "release" } else { "debug" };
+    let elapsed = Duration::from_micros(ran.elapsed.as_micros() as u64);
+    eprintln!(
+        "{year}-{day:0>2} ran in {} ({build} build)",
+        format_duration(elapsed)
+    );
+
+    if let Some(part) = part {
+        let answer = ran.answers.get(&part).ok_or_else(|| {
+            Error::msg(format!(
+                "{year}-{day:0>2} didn't print an answer for part {part}"
+            ))
+        })?;
+        let verdict = submit(&site, opts.wait_cooldown, &selector, part, answer, force)?;
+        match opts.json {
+            true => print_json(&json::Submission::new(&selector, part, answer, &verdict))?,
+            false => println!("{year}-{day:0>2} part {part}: {verdict}"),
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_await(opts: &Opts, year: Option<usize>, hook: Option<String>) -> Result<()> {
+    let hook = match hook {
+        Some(hook) => Some(hook),
+        None => Config::load()?.hooks.after_await,
+    };
+    let year = year.unwrap_or_else(|| schedule::next_event(Local::now().into()));
+    calendar::validate(year, 1)?;
+    let days = schedule::upcoming(year, Local::now().into());
+    if days.is_empty() {
+        return Err(Error::msg(format!(
+            "every day of {year} has unlocked already"
+        )));
+    }
+    let dir = match repo::root() {
+        Some(root) => root,
+        None => std::env::current_dir()?,
+    };
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
+
+    eprintln!(
+        "waiting for {} days of {year}, {}-{:0>2} is next",
+        days.len(),
+        year,
+        days[0].day()
+    );
+    let failures = schedule::run(&SystemClock, &days, print_countdown, |selector| {
+        eprintln!(
+            "{} ... fetching {}-{:0>2}",
+            Local::now(),
+            year,
+            selector.day()
+        );
+        fetch_unlocked(&inputs, selector, false)?;
+        polite(true, || site.get_or_fetch_puzzle(selector, false))?;
+        eprintln!(
+            "saved the input and puzzle to {}",
+            selector.dir()?.display()
+        );
+
+        if let Some(hook) = &hook {
+            schedule::run_hook(hook, selector, &dir)?;
         }
+        Ok(())
+    });
+
+    for (selector, err) in failures.iter() {
+        eprintln!("{}-{:0>2} failed: {err:#}", selector.year(), selector.day());
     }
+    if !failures.is_empty() {
+        return Err(Error::msg(format!("{} days failed", failures.len())));
+    }
+
+    Ok(())
+}
+
+fn cmd_watch(opts: &Opts, year: usize, day: usize) -> Result<()> {
+    let selector = Selector::new(year, day)?;
+    let root = repo_root("the day")?;
+    let manifest = run::manifest(&root, year, day)?;
+    let site = connect(opts.base_url)?;
+    let inputs = cached_inputs(opts.mirror, &site);
 
-    let mut attempts = 0;
-    let max_attempts = 10;
+    if let Err(err) = polite(opts.wait_cooldown, || inputs.get(&selector, false)) {
+        eprintln!("couldn't get the input, only running the examples: {err:#}");
+        sleep(Duration::from_secs(2));
+    }
 
+    let dirs = vec![
+        repo::day_dir(&root, year, day).join("src"),
+        repo::fixtures_dir(&root, year, day),
+    ];
+    let mut snapshot = Snapshot::take(&dirs);
+    let mut changed = vec![];
     loop {
-        let result = get_or_fetch_input(&selector, force);
-        if result.is_ok() || attempts >= max_attempts {
-            return result;
-        } else {
-            println!("attempt {} failed", attempts + 1);
-            attempts += 1;
-            sleep(Duration::from_millis(attempts * 200));
+        let panel = watch_panel(&root, &selector, &manifest, &changed);
+        // clear the screen and redraw in place
+        print!("\x1b[2J\x1b[H{}", panel.render());
+        println!("\nwatching for changes, ctrl-c to stop");
+        io::stdout().flush()?;
+
+        loop {
+            sleep(Duration::from_millis(500));
+            let next = Snapshot::take(&dirs);
+            changed = next.changed(&snapshot);
+            snapshot = next;
+            if !changed.is_empty() {
+                break;
+            }
         }
     }
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn cmd_bench(year: Option<usize>, day: Option<usize>, runs: usize, threshold: f64) -> Result<()> {
+    let root = repo_root("any days")?;
+    let days: Vec<Selector> = match (year, day) {
+        (Some(year), Some(day)) => vec![Selector::new(year, day)?],
+        (Some(year), None) => {
+            calendar::validate(year, 1)?;
+            solved_days(&root, vec![year])
+        }
+        (None, _) => solved_days(&root, repo::years(&root)),
+    };
+    let threshold = threshold / 100.0;
+
+    let mut history = bench::History::load()?;
+    let mut regressed = vec![];
+    let mut failures = vec![];
+    let mut uncached = 0;
+
+    println!("{}", bench::header());
+    for selector in days {
+        let (year, day) = (selector.year(), selector.day());
+        if !selector.exists() {
+            uncached += 1;
+            continue;
+        }
+
+        let sample = run::manifest(&root, year, day)
+            .and_then(|manifest| run::build_quiet(&manifest, true))
+            .and_then(|binary| bench::measure(&binary, year, day, runs));
+        match sample {
+            Ok(sample) => {
+                let previous = history.last(year, day).cloned();
+                println!("{}", bench::row(&sample, previous.as_ref(), threshold));
+                if let Some(previous) = previous {
+                    if bench::regressed(&previous, &sample, threshold) {
+                        regressed.push(selector);
+                    }
+                }
+                history.record(sample)?;
+            }
+            Err(err) => failures.push((selector, format!("{err:#}"))),
+        }
+    }
 
-    match cli.command {
-        Command::Get { year, day, wait } => {
-            let selector = Selector { year, day };
+    warn_uncached(uncached);
+    for (selector, reason) in failures.iter() {
+        eprintln!(
+            "{}-{:0>2} failed: {reason}",
+            selector.year(),
+            selector.day()
+        );
+    }
+    if !regressed.is_empty() {
+        let days: Vec<String> = regressed
+            .iter()
+            .map(|s| format!("{}-{:0>2}", s.year(), s.day()))
+            .collect();
+        eprintln!(
+            "{} regressed by more than {}%: {}",
+            regressed.len(),
+            threshold * 100.0,
+            days.join(" ")
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_verify(
+    opts: &Opts,
+    year: Option<usize>,
+    fetch: bool,
+    throttle: Option<humantime::Duration>,
+) -> Result<()> {
+    let throttle = match throttle {
+        Some(throttle) => throttle.into(),
+        None => Config::load()?.throttle("throttle.verify")?,
+    };
+    let root = repo_root("any days")?;
+    let years = match year {
+        Some(year) => {
+            calendar::validate(year, 1)?;
+            vec![year]
+        }
+        None => repo::years(&root),
+    };
+
+    let site = match fetch {
+        true => Some(connect(opts.base_url)?),
+        false => None,
+    };
+
+    let (mut passed, mut unknown, mut uncached) = (0, 0, 0);
+    let mut failed = vec![];
+
+    for selector in solved_days(&root, years) {
+        let (year, day) = (selector.year(), selector.day());
+        let mut known = Ledger::load(&selector)?.solutions();
+        let missing = known.len() < calendar::parts(year, day).count();
+        if let Some(site) = site.as_ref().filter(|_| missing) {
+            polite(opts.wait_cooldown, || {
+                site.get_or_fetch_puzzle(&selector, true)
+            })?;
+            sleep(throttle);
+            known = Ledger::load(&selector)?.solutions();
+        }
+
+        if known.is_empty() {
+            unknown += 1;
+            continue;
+        }
+        if !selector.exists() {
+            uncached += 1;
+            continue;
+        }
 
-            if wait {
-                wait_for(&selector, false)?;
-            } else {
-                get_or_fetch_input(&selector, false)?;
+        let ran = run::manifest(&root, year, day)
+            .and_then(|manifest| run::build_quiet(&manifest, true))
+            .and_then(|binary| run::run_quiet(&binary));
+        match ran {
+            Ok(ran) => {
+                let check = DayCheck::new(year, day, &known, &ran.answers);
+                println!("{check}");
+                match check.passed() {
+                    true => passed += 1,
+                    false => failed.push(selector),
+                }
+            }
+            Err(err) => {
+                println!("{year}-{day:0>2} failed: {err:#}");
+                failed.push(selector);
             }
+        }
+    }
+
+    eprintln!("{passed} passed, {} failed", failed.len());
+    if unknown > 0 {
+        eprintln!(
+            "{unknown} days have no known answers, `advent verify --fetch` picks them up from the puzzle pages"
+        );
+    }
+    warn_uncached(uncached);
+    if !failed.is_empty() {
+        let days: Vec<String> = failed
+            .iter()
+            .map(|s| format!("{}-{:0>2}", s.year(), s.day()))
+            .collect();
+        return Err(Error::msg(format!(
+            "{} failed: {}",
+            failed.len(),
+            days.join(" ")
+        )));
+    }
+
+    Ok(())
+}
+
+fn cmd_set_cookie(cookie: Option<String>) -> Result<()> {
+    let cookie = match cookie {
+        Some(value) => value,
+        None => prompt_password("Your cookie value: ")?,
+    };
+    let cookie = if !cookie.starts_with("session=") {
+        format!("session={cookie}")
+    } else {
+        cookie
+    };
 
-            eprintln!("success: fetched {year}-{day:0>2}");
+    set_cookie(cookie, true)?;
+
+    Ok(())
+}
+
+fn cmd_status(opts: &Opts, year: Option<usize>) -> Result<()> {
+    let years = match year {
+        Some(year) => {
+            calendar::validate(year, 1)?;
+            year..=year
         }
-        Command::GetYear { year } => {
-            get_all_inputs(year, false)?;
-            eprintln!("success: fetched all of year {}", year)
+        None => calendar::event_years(),
+    };
+    let root = repo::root();
+    if root.is_none() {
+        eprintln!("warning: not in an advent-rs checkout, can't see any solutions");
+    }
+
+    let site = match input_store::has_cookie() {
+        true => Some(connect(opts.base_url)?),
+        false => {
+            eprintln!("warning: no cookie set, can't see any stars");
+            None
         }
-        Command::Show { year, day, wait } => {
-            let selector = Selector { year, day };
+    };
+    let mut ask_site = site.is_some();
+    let throttle = Config::load()?.throttle("throttle.sync")?;
 
-            let value = if wait {
-                wait_for(&selector, false)?
-            } else {
-                get_or_fetch_input(&selector, false)?
-            };
+    let mut statuses = vec![];
+    for year in years {
+        let stars = match site.as_ref().filter(|_| ask_site) {
+            Some(site) => match polite(opts.wait_cooldown, || site.get_or_fetch_stars(year)) {
+                Ok((stars, age)) => {
+                    if age.is_zero() {
+                        sleep(throttle);
+                    }
+                    Some(stars)
+                }
+                Err(err) => {
+                    eprintln!("warning: can't get stars, {err:#}");
+                    ask_site = false;
+                    None
+                }
+            },
+            None => None,
+        };
+        let solutions = calendar::days(year)
+            .map(|day| {
+                let solution = match &root {
+                    Some(root) => repo::solution(root, year, day),
+                    None => Solution::Missing,
+                };
+                (day, solution)
+            })
+            .collect();
 
-            println!("{value}");
+        let status = YearStatus {
+            year,
+            stars,
+            solutions,
+        };
+        if opts.json {
+            statuses.push(json::Year::from(&status));
+        } else {
+            println!("{}", status.render());
         }
-        Command::SetCookie { cookie } => {
-            let cookie = match cookie {
-                Some(value) => value,
-                None => prompt_password("Your cookie value: ")?,
-            };
-            let cookie = if !cookie.starts_with("session=") {
-                format!("session={cookie}")
-            } else {
-                cookie
-            };
+    }
+    if opts.json {
+        print_json(&statuses)?;
+    }
+
+    Ok(())
+}
 
-            set_cookie(cookie, true)?;
+fn cmd_whoami(opts: &Opts, profile: &str) -> Result<()> {
+    input_store::get_cookie()?;
+    let site = connect(opts.base_url)?;
+    let user = polite(opts.wait_cooldown, || site.whoami())?;
+    println!("{user} (profile {profile})");
+
+    Ok(())
+}
+
+fn cmd_path(opts: &Opts, year: Option<usize>, day: Option<usize>) -> Result<()> {
+    match (year, day) {
+        (Some(year), Some(day)) => {
+            let selector = Selector::new(year, day)?;
+            match opts.json {
+                true => print_json(&json::DayInput::load(&selector)?)?,
+                false => println!("{}", selector.filename()?.display()),
+            }
+        }
+        _ => {
+            let paths = json::ProfilePaths::active()?;
+            match opts.json {
+                true => print_json(&paths)?,
+                false => println!("{}", paths.inputs.display()),
+            }
         }
     }
 
     Ok(())
 }
+
+fn cmd_profiles() -> Result<()> {
+    let active = input_store::active_profile();
+    for profile in input_store::profiles()? {
+        let marker = if profile == active { "*" } else { " " };
+        println!("{marker} {profile}");
+    }
+
+    Ok(())
+}
@@ -26,7 +26,7 @@ pub fn dijkstra<T>(
     initial: &[T],
     // goal: &T,
     edges: impl Fn(&T) -> Vec<T>,
-    is_goal: impl Fn(&T) -> bool,
+    _is_goal: impl Fn(&T) -> bool,
     cost_fn: Option<impl Fn(&T) -> Option<usize>>,
 ) -> DijkstraResult<T>
 where
@@ -38,7 +38,7 @@ struct State {
 impl Machine for State {
     type Action = Instruction;
 
-    fn apply_mut(&mut self, action: Instruction) {
+    fn apply_mut(&mut self, _action: Instruction) {
         self.cursor += 1;
         todo!()
     }
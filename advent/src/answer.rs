@@ -0,0 +1,201 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::html;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Hint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Hint::TooHigh => write!(f, "too high"),
+            Hint::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    AlreadySolved,
+    RateLimited {
+        wait: Duration,
+    },
+    Unknown(String),
+}
+
+impl Verdict {
+    pub fn from_html(body: &str) -> Self {
+        let text = html::article_text(body);
+        Self::from_text(&text)
+    }
+
+    pub fn from_text(text: &str) -> Self {
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Verdict::Wrong {
+                hint,
+                wait: parse_wait(text, "wait ", " before trying again"),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited {
+                wait: parse_wait(text, "You have ", " left to wait").unwrap_or_default(),
+            }
+        } else if text.contains("Did you already complete it?") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(text.trim().to_string())
+        }
+    }
+
+    pub fn is_correct(&self) -> bool {
+        matches!(self, Verdict::Correct)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct! ⭐"),
+            Verdict::Wrong { hint, wait } => {
+                write!(f, "wrong")?;
+                if let Some(hint) = hint {
+                    write!(f, ", {}", hint)?;
+                }
+                if let Some(wait) = wait {
+                    write!(f, " (wait {} before trying again)", format_wait(*wait))?;
+                }
+                Ok(())
+            }
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::RateLimited { wait } => {
+                write!(f, "rate limited, {} left to wait", format_wait(*wait))
+            }
+            Verdict::Unknown(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+pub fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    if secs >= 3600 {
+        return format!("{}h {}m", secs / 3600, secs % 3600 / 60);
+    }
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{}s", s),
+        (m, 0) => format!("{}m", m),
+        (m, s) => format!("{}m {}s", m, s),
+    }
+}
+
+fn word_number(word: &str) -> Option<u64> {
+    let n = match word {
+        "a" | "an" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        _ => return word.parse().ok(),
+    };
+    Some(n)
+}
+
+// handles "one minute", "5 minutes", "34s" and "1m 4s"
+fn parse_duration(phrase: &str) -> Option<Duration> {
+    let mut secs = 0;
+    let mut found = false;
+    let mut words = phrase.split_whitespace().peekable();
+
+    while let Some(word) = words.next() {
+        let word = word.to_lowercase();
+        if let Some(n) = word_number(&word) {
+            let unit = words.peek().map(|u| u.to_lowercase());
+            let scale = match unit.as_deref() {
+                Some("second" | "seconds") => 1,
+                Some("minute" | "minutes") => 60,
+                Some("hour" | "hours") => 3600,
+                _ => continue,
+            };
+            words.next();
+            secs += n * scale;
+            found = true;
+            continue;
+        }
+
+        let (digits, unit) = word.split_at(word.find(|c: char| !c.is_ascii_digit()).unwrap_or(0));
+        let scale = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            _ => continue,
+        };
+        if let Ok(n) = digits.parse::<u64>() {
+            secs += n * scale;
+            found = true;
+        }
+    }
+
+    found.then(|| Duration::from_secs(secs))
+}
+
+fn parse_wait(text: &str, prefix: &str, suffix: &str) -> Option<Duration> {
+    let end = text.find(suffix)?;
+    let start = text[..end].rfind(prefix)? + prefix.len();
+    parse_duration(&text[start..end])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(include_str!("../fixtures/answer/correct.html"), Verdict::Correct)]
+    #[case(include_str!("../fixtures/answer/too_high.html"), Verdict::Wrong { hint: Some(Hint::TooHigh), wait: Some(Duration::from_secs(60)) })]
+    #[case(include_str!("../fixtures/answer/too_low.html"), Verdict::Wrong { hint: Some(Hint::TooLow), wait: Some(Duration::from_secs(300)) })]
+    #[case(include_str!("../fixtures/answer/wrong.html"), Verdict::Wrong { hint: None, wait: Some(Duration::from_secs(60)) })]
+    #[case(include_str!("../fixtures/answer/already_solved.html"), Verdict::AlreadySolved)]
+    #[case(include_str!("../fixtures/answer/rate_limited.html"), Verdict::RateLimited { wait: Duration::from_secs(34) })]
+    #[case(include_str!("../fixtures/answer/rate_limited_minutes.html"), Verdict::RateLimited { wait: Duration::from_secs(274) })]
+    fn test_verdicts(#[case] given: &str, #[case] expected: Verdict) {
+        assert_eq!(Verdict::from_html(given), expected);
+    }
+
+    #[test]
+    fn test_unknown() {
+        let verdict = Verdict::from_html("<main><article><p>Something new.</p></article></main>");
+        assert_eq!(verdict, Verdict::Unknown("Something new.".to_string()));
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(34), "34s")]
+    #[case(Duration::from_secs(60), "1m")]
+    #[case(Duration::from_secs(274), "4m 34s")]
+    #[case(Duration::from_secs(7380), "2h 3m")]
+    fn test_format_wait(#[case] given: Duration, #[case] expected: &str) {
+        assert_eq!(format_wait(given), expected);
+    }
+}
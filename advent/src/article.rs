@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use crate::html::{self, Node};
+
+const SITE: &str = "https://adventofcode.com";
+
+const BOLD: &str = "\x1b[1m";
+const UNDERLINE: &str = "\x1b[4m";
+const HIGHLIGHT: &str = "\x1b[1;93m";
+const CODE: &str = "\x1b[96m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    Markdown,
+    Ansi,
+}
+
+/// the `<article class="day-desc">` blocks of a puzzle page, one per unlocked part
+pub fn descriptions(page: &str) -> Vec<Node> {
+    html::articles(page)
+        .into_iter()
+        .filter(|a| a.has_class("day-desc"))
+        .collect()
+}
+
+/// the answers a solved puzzle's page shows, "Your puzzle answer was ...", numbered by part
+pub fn answers(page: &str) -> BTreeMap<u16, String> {
+    html::find_all(&html::parse(page), "p")
+        .into_iter()
+        .filter(|p| p.text().starts_with("Your puzzle answer was"))
+        .filter_map(|p| p.find_all("code").first().map(|code| code.text()))
+        .zip(1..)
+        .map(|(answer, part)| (part, answer.trim().to_string()))
+        .collect()
+}
+
+pub fn render_page(page: &str, style: Style) -> String {
+    descriptions(page)
+        .iter()
+        .map(|article| render(article, style))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render(article: &Node, style: Style) -> String {
+    let mut out = String::new();
+    for child in article.children() {
+        block(child, style, &mut out);
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+fn block(node: &Node, style: Style, out: &mut String) {
+    match node {
+        Node::Text(t) if t.trim().is_empty() => {}
+        Node::Text(_) => {
+            out.push_str(inline(node, style).trim());
+            out.push_str("\n\n");
+        }
+        Node::Element { name, children, .. } => match name.as_str() {
+            "h2" => {
+                let title = node.text();
+                let title = title.trim().trim_matches('-').trim();
+                match style {
+                    Style::Markdown => out.push_str(&format!("## {}\n\n", title)),
+                    Style::Ansi => out.push_str(&format!("{BOLD}{}{RESET}\n\n", title)),
+                }
+            }
+            "pre" => {
+                let text = node.text();
+                let text = text.trim_end_matches('\n');
+                match style {
+                    Style::Markdown => out.push_str(&format!("```\n{}\n```\n\n", text)),
+                    Style::Ansi => {
+                        for line in text.lines() {
+                            out.push_str(&format!("    {CODE}{}{RESET}\n", line));
+                        }
+                        out.push('\n');
+                    }
+                }
+            }
+            "ul" | "ol" => {
+                let items = children.iter().filter(|c| c.is("li"));
+                for (idx, item) in items.enumerate() {
+                    let marker = match name.as_str() {
+                        "ol" => format!("{}.", idx + 1),
+                        _ => "-".to_string(),
+                    };
+                    out.push_str(&format!("{} {}\n", marker, inline_children(item, style)));
+                }
+                out.push('\n');
+            }
+            "p" | "li" | "div" => {
+                out.push_str(&inline_children(node, style));
+                out.push_str("\n\n");
+            }
+            _ => {
+                out.push_str(inline(node, style).trim());
+                out.push_str("\n\n");
+            }
+        },
+    }
+}
+
+fn collapse(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_space {
+                out.push(' ');
+            }
+            last_space = true;
+        } else {
+            out.push(c);
+            last_space = false;
+        }
+    }
+    out
+}
+
+fn inline_children(node: &Node, style: Style) -> String {
+    let text: String = node.children().iter().map(|c| inline(c, style)).collect();
+    text.lines().map(str::trim).collect::<Vec<_>>().join("\n")
+}
+
+fn inline(node: &Node, style: Style) -> String {
+    let name = match node {
+        Node::Text(t) => return collapse(t),
+        Node::Element { name, .. } => name,
+    };
+
+    let inner = || -> String { node.children().iter().map(|c| inline(c, style)).collect() };
+
+    match (name.as_str(), style) {
+        ("br", _) => "\n".to_string(),
+        ("code", Style::Markdown) => {
+            let text = node.text();
+            let fence = if text.contains('`') { "``" } else { "`" };
+            format!("{fence}{}{fence}", text)
+        }
+        ("code", Style::Ansi) => format!("{CODE}{}{RESET}", node.text()),
+        ("em", Style::Markdown) => format!("*{}*", inner()),
+        ("em", Style::Ansi) => format!("{HIGHLIGHT}{}{RESET}", inner()),
+        ("a", Style::Markdown) => match node.attr("href") {
+            Some(href) => format!("[{}]({})", inner(), absolute(href)),
+            None => inner(),
+        },
+        ("a", Style::Ansi) => format!("{UNDERLINE}{}{RESET}", inner()),
+        _ => inner(),
+    }
+}
+
+fn absolute(href: &str) -> String {
+    if href.starts_with('/') {
+        format!("{}{}", SITE, href)
+    } else {
+        href.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = include_str!("../fixtures/puzzle/both_parts.html");
+
+    #[test]
+    fn test_descriptions() {
+        assert_eq!(descriptions(PAGE).len(), 2);
+        let part_1 = include_str!("../fixtures/puzzle/part_1.html");
+        assert_eq!(descriptions(part_1).len(), 1);
+    }
+
+    #[test]
+    fn test_answers() {
+        let answers = answers(PAGE);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[&1], "1154");
+        assert_eq!(answers[&2], "1127");
+
+        let part_1 = include_str!("../fixtures/puzzle/part_1.html");
+        assert!(super::answers(part_1).is_empty());
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let rendered = render(&descriptions(PAGE)[0], Style::Markdown);
+        let expected = r#"## Day 1: Sonar Sweep Redux
+
+The elves are counting *depth readings* again. Each line of the [report](https://adventofcode.com/2022/day/1/input) holds one number:
+
+```
+199
+200
+208
+```
+
+- Count how many readings are *larger* than the one before.
+- Ignore the first reading.
+
+In this example, the answer is `2`. *How many readings are larger?*
+"#;
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let rendered = render(&descriptions(PAGE)[1], Style::Ansi);
+        assert!(rendered.starts_with(&format!("{BOLD}Part Two{RESET}\n\n")));
+        assert!(rendered.contains(&format!("{HIGHLIGHT}windows of three{RESET}")));
+        assert!(rendered.contains(&format!("    {CODE}607{RESET}\n")));
+    }
+}
@@ -0,0 +1,238 @@
+use std::fs::{DirBuilder, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AdventError;
+use crate::input_store;
+use crate::run;
+
+// anything quicker than this is noise, however big the percentage
+const MIN_REGRESSION: Duration = Duration::from_millis(1);
+
+/// one `advent bench` of a day, every time the median over its runs
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sample {
+    pub year: usize,
+    pub day: usize,
+    pub timestamp: u64,
+    pub runs: usize,
+    /// from the start until part 1's answer was printed, parsing included
+    pub part_1: Option<Duration>,
+    /// from part 1's answer until part 2's
+    pub part_2: Option<Duration>,
+    pub total: Duration,
+}
+
+pub fn median(mut times: Vec<Duration>) -> Option<Duration> {
+    times.sort();
+    times.get(times.len() / 2).copied()
+}
+
+/// runs `binary` once to warm up, then `runs` more times
+pub fn measure(binary: &Path, year: usize, day: usize, runs: usize) -> Result<Sample> {
+    run::run_quiet(binary)?;
+
+    let mut part_1 = vec![];
+    let mut part_2 = vec![];
+    let mut total = vec![];
+    for _ in 0..runs.max(1) {
+        let ran = run::run_quiet(binary)?;
+        part_1.extend(ran.parts.get(&1));
+        part_2.extend(ran.parts.get(&2));
+        total.push(ran.elapsed);
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    Ok(Sample {
+        year,
+        day,
+        timestamp,
+        runs: total.len(),
+        part_1: median(part_1),
+        part_2: median(part_2),
+        total: median(total).unwrap_or_default(),
+    })
+}
+
+/// every sample so far for the active profile, oldest first
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub samples: Vec<Sample>,
+}
+
+impl History {
+    pub fn load() -> Result<Self> {
+        let path = input_store::bench_history_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(&path).map_err(AdventError::io(&path))?;
+        let mut samples = vec![];
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            samples.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(Self { samples })
+    }
+
+    pub fn record(&mut self, sample: Sample) -> Result<()> {
+        let path = input_store::bench_history_path()?;
+        if let Some(dir) = path.parent() {
+            DirBuilder::new()
+                .recursive(true)
+                .create(dir)
+                .map_err(AdventError::io(dir))?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(AdventError::io(&path))?;
+        writeln!(file, "{}", serde_json::to_string(&sample)?)?;
+
+        self.samples.push(sample);
+        Ok(())
+    }
+
+    pub fn last(&self, year: usize, day: usize) -> Option<&Sample> {
+        self.samples
+            .iter()
+            .rev()
+            .find(|s| s.year == year && s.day == day)
+    }
+}
+
+/// how much slower `current` is than `previous`, eg. `0.25` for 25% slower
+pub fn change(previous: &Sample, current: &Sample) -> f64 {
+    let previous = previous.total.as_secs_f64();
+    if previous == 0.0 {
+        return 0.0;
+    }
+    (current.total.as_secs_f64() - previous) / previous
+}
+
+/// `threshold` is a fraction, `0.1` flags anything more than 10% slower
+pub fn regressed(previous: &Sample, current: &Sample, threshold: f64) -> bool {
+    current.total > previous.total + MIN_REGRESSION && change(previous, current) > threshold
+}
+
+pub fn format_time(time: Duration) -> String {
+    let secs = time.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else if secs >= 0.001 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.0}µs", secs * 1e6)
+    }
+}
+
+pub fn header() -> String {
+    format!(
+        "{:<8} {:>10} {:>10} {:>10} {:>10} {:>8}",
+        "day", "part 1", "part 2", "total", "previous", "change"
+    )
+}
+
+pub fn row(current: &Sample, previous: Option<&Sample>, threshold: f64) -> String {
+    let part = |time: Option<Duration>| time.map(format_time).unwrap_or_else(|| "-".into());
+    let (before, change, flag) = match previous {
+        Some(previous) => (
+            format_time(previous.total),
+            format!("{:+.1}%", change(previous, current) * 100.0),
+            match regressed(previous, current, threshold) {
+                true => " regressed",
+                false => "",
+            },
+        ),
+        None => ("-".into(), "-".into(), ""),
+    };
+
+    format!(
+        "{:<8} {:>10} {:>10} {:>10} {:>10} {:>8}{}",
+        format!("{}-{:02}", current.year, current.day),
+        part(current.part_1),
+        part(current.part_2),
+        format_time(current.total),
+        before,
+        change,
+        flag
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    fn sample(total_ms: u64) -> Sample {
+        Sample {
+            year: 2022,
+            day: 1,
+            timestamp: 0,
+            runs: 5,
+            part_1: Some(Duration::from_millis(total_ms / 2)),
+            part_2: None,
+            total: Duration::from_millis(total_ms),
+        }
+    }
+
+    #[rstest]
+    #[case(100, 105, false)]
+    #[case(100, 120, true)]
+    #[case(100, 80, false)]
+    // too quick to tell
+    #[case(1, 2, false)]
+    fn test_regressed(#[case] before: u64, #[case] after: u64, #[case] expected: bool) {
+        assert_eq!(regressed(&sample(before), &sample(after), 0.1), expected);
+    }
+
+    #[test]
+    fn test_median() {
+        let times = vec![3, 1, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        assert_eq!(median(times), Some(Duration::from_millis(2)));
+        assert_eq!(median(vec![]), None);
+    }
+
+    #[test]
+    fn test_row() {
+        let row = row(&sample(120), Some(&sample(100)), 0.1);
+        assert_eq!(
+            row,
+            "2022-01     60.00ms          -   120.00ms   100.00ms   +20.0% regressed"
+        );
+    }
+
+    #[test]
+    fn test_history() {
+        let (_guard, dir) = input_store::temp_config("bench");
+
+        let mut history = History::load().unwrap();
+        history.record(sample(100)).unwrap();
+        history.record(sample(90)).unwrap();
+
+        let history = History::load().unwrap();
+        assert_eq!(history.samples.len(), 2);
+        assert_eq!(history.last(2022, 1), Some(&sample(90)));
+        assert_eq!(history.last(2022, 2), None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
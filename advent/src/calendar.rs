@@ -0,0 +1,171 @@
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AdventError;
+
+pub const FIRST_YEAR: usize = 2015;
+
+// events from 2025 on are 12 days long
+const SHORT_EVENTS_FROM: usize = 2025;
+
+// puzzles unlock at midnight in UTC-5
+const UNLOCK_OFFSET: Duration = Duration::from_secs(5 * 60 * 60);
+
+/// how many days `year`'s event has. the next event counts once the last one has started, so it
+/// can be waited for, but not any later ones
+pub fn days_in(year: usize) -> Option<usize> {
+    days_in_at(year, SystemTime::now())
+}
+
+pub fn days_in_at(year: usize, now: SystemTime) -> Option<usize> {
+    let next = *event_years_at(now).end() + 1;
+    match year {
+        y if y < FIRST_YEAR || y > next => None,
+        y if y < SHORT_EVENTS_FROM => Some(25),
+        _ => Some(12),
+    }
+}
+
+pub fn days(year: usize) -> RangeInclusive<usize> {
+    1..=days_in(year).unwrap_or(0)
+}
+
+pub fn is_valid(year: usize, day: usize) -> bool {
+    days(year).contains(&day)
+}
+
+pub fn validate(year: usize, day: usize) -> Result<(), AdventError> {
+    if is_valid(year, day) {
+        Ok(())
+    } else {
+        Err(AdventError::NoSuchDay { year, day })
+    }
+}
+
+/// the last day only has one puzzle, its second star comes from finishing every other day
+pub fn is_final_day(year: usize, day: usize) -> bool {
+    days_in(year) == Some(day)
+}
+
+pub fn parts(year: usize, day: usize) -> RangeInclusive<u16> {
+    match is_final_day(year, day) {
+        true => 1..=1,
+        false => 1..=2,
+    }
+}
+
+// days from 1970-01-01 to a date in the proleptic gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+pub fn unlocks_at(year: usize, day: usize) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    UNIX_EPOCH + Duration::from_secs(days as u64 * 24 * 60 * 60) + UNLOCK_OFFSET
+}
+
+/// how long until `year`-`day` unlocks, or `None` if it already has
+pub fn until_unlock(year: usize, day: usize, now: SystemTime) -> Option<Duration> {
+    unlocks_at(year, day)
+        .duration_since(now)
+        .ok()
+        .filter(|d| !d.is_zero())
+}
+
+pub fn check_unlocked(year: usize, day: usize) -> Result<(), AdventError> {
+    match until_unlock(year, day, SystemTime::now()) {
+        Some(remaining) => Err(AdventError::NotUnlocked {
+            year,
+            day,
+            remaining,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// every event that has at least started by `now`
+pub fn event_years_at(now: SystemTime) -> RangeInclusive<usize> {
+    let mut last = FIRST_YEAR;
+    while unlocks_at(last + 1, 1) <= now {
+        last += 1;
+    }
+    FIRST_YEAR..=last
+}
+
+pub fn event_years() -> RangeInclusive<usize> {
+    event_years_at(SystemTime::now())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[rstest]
+    #[case(2014, None)]
+    #[case(2015, Some(25))]
+    #[case(2024, Some(25))]
+    #[case(2025, Some(12))]
+    fn test_days_in(#[case] year: usize, #[case] expected: Option<usize>) {
+        assert_eq!(days_in(year), expected);
+    }
+
+    #[rstest]
+    #[case(2022, 1, true)]
+    #[case(2022, 25, true)]
+    #[case(2022, 26, false)]
+    #[case(2022, 0, false)]
+    #[case(2025, 12, true)]
+    #[case(2025, 13, false)]
+    #[case(2014, 1, false)]
+    #[case(2099, 1, false)]
+    fn test_is_valid(#[case] year: usize, #[case] day: usize, #[case] expected: bool) {
+        assert_eq!(is_valid(year, day), expected);
+    }
+
+    #[test]
+    fn test_final_day() {
+        assert!(is_final_day(2022, 25));
+        assert!(is_final_day(2025, 12));
+        assert!(!is_final_day(2025, 25));
+        assert_eq!(parts(2022, 25), 1..=1);
+        assert_eq!(parts(2022, 24), 1..=2);
+    }
+
+    #[test]
+    fn test_unlocks_at() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlocks_at(2022, 1), at(1669870800));
+        assert_eq!(unlocks_at(2015, 25), at(1451019600));
+        assert_eq!(
+            until_unlock(2022, 1, at(1669870800 - 90)),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(until_unlock(2022, 1, at(1669870800)), None);
+    }
+
+    #[test]
+    fn test_event_years() {
+        assert_eq!(event_years_at(at(1669870800 - 1)), 2015..=2021);
+        assert_eq!(event_years_at(at(1669870800)), 2015..=2022);
+    }
+
+    #[test]
+    fn test_days_in_bounded_by_events() {
+        // 2022-12-01, so 2023 is the next event
+        let now = at(1669870800);
+        assert_eq!(days_in_at(2022, now), Some(25));
+        assert_eq!(days_in_at(2023, now), Some(25));
+        assert_eq!(days_in_at(2024, now), None);
+    }
+}
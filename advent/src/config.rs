@@ -0,0 +1,415 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs};
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AdventError;
+use crate::input_store::{self, DEFAULT_PROFILE};
+use crate::repo;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_USER_AGENT: &str = "github.com/kryptn/advent-rs";
+pub const DEFAULT_THROTTLE: Duration = Duration::from_secs(2);
+
+pub struct Key {
+    pub name: &'static str,
+    /// the environment variable that beats the file, if there is one
+    pub env: Option<&'static str>,
+    pub about: &'static str,
+}
+
+/// everything `config.toml` can set
+pub const KEYS: &[Key] = &[
+    Key {
+        name: "profile",
+        env: Some("AOC_PROFILE"),
+        about: "the profile to use when --profile isn't given",
+    },
+    Key {
+        name: "base_url",
+        env: Some("AOC_BASE_URL"),
+        about: "where adventofcode.com is",
+    },
+    Key {
+        name: "repo_root",
+        env: None,
+        about: "the advent-rs checkout with the YEAR/dayNN crates, instead of looking up from here",
+    },
+    Key {
+        name: "template_dir",
+        env: None,
+        about: "where `advent new` finds its templates, instead of the checkout's template/",
+    },
+    Key {
+        name: "user_agent",
+        env: None,
+        about: "sent with every request to the site",
+    },
+    Key {
+        name: "contact",
+        env: None,
+        about: "an email added to the user agent, so the site can reach you about your traffic",
+    },
+    Key {
+        name: "throttle.sync",
+        env: None,
+        about: "the wait between requests for sync, get-year and status",
+    },
+    Key {
+        name: "throttle.verify",
+        env: None,
+        about: "the wait between puzzle fetches for verify --fetch",
+    },
+    Key {
+        name: "hooks.await",
+        env: None,
+        about:
+            "run after `advent await` fetches a day, with {{year}}, {{day}} and {{name}} filled in",
+    },
+    Key {
+        name: "hooks.new",
+        env: None,
+        about:
+            "run after `advent new` creates a day, with {{year}}, {{day}} and {{name}} filled in",
+    },
+];
+
+fn key(name: &str) -> Result<&'static Key, AdventError> {
+    KEYS.iter()
+        .find(|k| k.name == name)
+        .ok_or_else(|| AdventError::UnknownConfigKey {
+            key: name.to_string(),
+        })
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Throttle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hooks {
+    #[serde(default, rename = "await", skip_serializing_if = "Option::is_none")]
+    pub after_await: Option<String>,
+    #[serde(default, rename = "new", skip_serializing_if = "Option::is_none")]
+    pub after_new: Option<String>,
+}
+
+/// `$AOC_CONFIG/config.toml`, shared by every profile
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_root: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    #[serde(default)]
+    pub throttle: Throttle,
+    #[serde(default)]
+    pub hooks: Hooks,
+}
+
+/// where a setting's value came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Flag(&'static str),
+    Env(&'static str),
+    File,
+    Default,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Flag(flag) => write!(f, "{}", flag),
+            Source::Env(var) => write!(f, "${}", var),
+            Source::File => write!(f, "config.toml"),
+            Source::Default => write!(f, "default"),
+        }
+    }
+}
+
+fn parse_duration(key: &str, value: &str) -> Result<Duration> {
+    humantime::parse_duration(value)
+        .map_err(|err| Error::msg(format!("{} should be a duration like 2s: {}", key, err)))
+}
+
+impl Config {
+    /// the saved config, or an empty one if there's no file yet
+    pub fn load() -> Result<Self> {
+        let path = input_store::config_filename()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path).map_err(AdventError::io(&path))?;
+        toml::from_str(&text).map_err(|err| {
+            AdventError::BadConfig {
+                path,
+                reason: err.message().to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// like `load`, but a file that can't be parsed reads as empty, along with why. for the
+    /// commands that manage the file, so they can be used to fix it
+    pub fn load_lenient() -> Result<(Self, Option<AdventError>)> {
+        match Self::load() {
+            Ok(config) => Ok((config, None)),
+            Err(err) => match err.downcast::<AdventError>()? {
+                err @ AdventError::BadConfig { .. } => Ok((Self::default(), Some(err))),
+                err => Err(err.into()),
+            },
+        }
+    }
+
+    /// moves config.toml to config.toml.bak, so a broken one can be started over
+    pub fn set_aside() -> Result<PathBuf> {
+        let path = input_store::config_filename()?;
+        let backup = path.with_extension("toml.bak");
+        fs::rename(&path, &backup).map_err(AdventError::io(&path))?;
+        Ok(backup)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = input_store::config_filename()?;
+        input_store::write_file(path, &toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn slot(&mut self, name: &str) -> Result<&mut Option<String>, AdventError> {
+        let slot = match key(name)?.name {
+            "profile" => &mut self.profile,
+            "base_url" => &mut self.base_url,
+            "repo_root" => &mut self.repo_root,
+            "template_dir" => &mut self.template_dir,
+            "user_agent" => &mut self.user_agent,
+            "contact" => &mut self.contact,
+            "throttle.sync" => &mut self.throttle.sync,
+            "throttle.verify" => &mut self.throttle.verify,
+            "hooks.await" => &mut self.hooks.after_await,
+            "hooks.new" => &mut self.hooks.after_new,
+            _ => unreachable!("every key has a slot"),
+        };
+        Ok(slot)
+    }
+
+    /// what the file says for `key`
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.clone().slot(key)?.clone())
+    }
+
+    /// sets `key` in the file, or removes it with `None`
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        if let Some(value) = value {
+            match key {
+                "profile" => input_store::validate_profile(value)?,
+                k if k.starts_with("throttle.") => {
+                    parse_duration(k, value)?;
+                }
+                _ => {}
+            }
+        }
+        *self.slot(key)? = value.map(str::to_string);
+        Ok(())
+    }
+
+    /// `key`'s value from its environment variable or the file, whichever comes first
+    pub fn lookup(&self, name: &str) -> Result<Option<(String, Source)>> {
+        let key = key(name)?;
+        if let Some(var) = key.env {
+            if let Ok(value) = env::var(var) {
+                if !value.is_empty() {
+                    return Ok(Some((value, Source::Env(var))));
+                }
+            }
+        }
+        Ok(self.get(name)?.map(|value| (value, Source::File)))
+    }
+
+    /// `key`'s value, falling back to what's used when nothing sets it
+    pub fn effective(&self, name: &str) -> Result<Option<(String, Source)>> {
+        if let Some(found) = self.lookup(name)? {
+            return Ok(Some(found));
+        }
+        let default = match name {
+            "profile" => Some(DEFAULT_PROFILE.to_string()),
+            "base_url" => Some(DEFAULT_BASE_URL.to_string()),
+            "repo_root" => repo::search_root().map(|r| r.display().to_string()),
+            "template_dir" => self
+                .repo_root()
+                .or_else(repo::search_root)
+                .map(|r| r.join("template").display().to_string()),
+            "user_agent" => Some(self.user_agent()),
+            k if k.starts_with("throttle.") => {
+                Some(humantime::format_duration(DEFAULT_THROTTLE).to_string())
+            }
+            _ => None,
+        };
+        Ok(default.map(|value| (value, Source::Default)))
+    }
+
+    pub fn base_url(&self) -> Result<String> {
+        Ok(self
+            .lookup("base_url")?
+            .map(|(url, _)| url)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()))
+    }
+
+    /// the configured checkout, if there is one
+    pub fn repo_root(&self) -> Option<PathBuf> {
+        self.repo_root.as_ref().map(PathBuf::from)
+    }
+
+    pub fn template_dir(&self) -> Option<PathBuf> {
+        self.template_dir.as_ref().map(PathBuf::from)
+    }
+
+    /// `user_agent`, with `contact` after it when that's set
+    pub fn user_agent(&self) -> String {
+        let agent = self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+        match &self.contact {
+            Some(contact) => format!("{} by {}", agent, contact),
+            None => agent.to_string(),
+        }
+    }
+
+    /// one of the `throttle.*` keys, or the default wait
+    pub fn throttle(&self, key: &str) -> Result<Duration> {
+        match self.get(key)? {
+            Some(value) => parse_duration(key, &value),
+            None => Ok(DEFAULT_THROTTLE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_and_save() {
+        let (_guard, dir) = input_store::temp_config("config");
+
+        assert_eq!(Config::load().unwrap(), Config::default());
+
+        let mut config = Config::default();
+        config.set("contact", Some("me@example.com")).unwrap();
+        config.set("throttle.sync", Some("5s")).unwrap();
+        config
+            .set("hooks.await", Some("just prebuild-day {{year}} {{day}}"))
+            .unwrap();
+        config.save().unwrap();
+
+        let saved = fs::read_to_string(dir.join("config.toml")).unwrap();
+        assert!(saved.contains("[hooks]\nawait = \"just prebuild-day {{year}} {{day}}\""));
+
+        let loaded = Config::load().unwrap();
+        assert_eq!(loaded, config);
+        assert_eq!(
+            loaded.throttle("throttle.sync").unwrap(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            loaded.throttle("throttle.verify").unwrap(),
+            DEFAULT_THROTTLE
+        );
+        assert_eq!(
+            loaded.user_agent(),
+            format!("{} by me@example.com", DEFAULT_USER_AGENT)
+        );
+
+        fs::write(dir.join("config.toml"), "nope = 1\n").unwrap();
+        let err = Config::load().unwrap_err();
+        assert!(matches!(
+            crate::error::advent_error(&err),
+            Some(AdventError::BadConfig { .. })
+        ));
+
+        let (lenient, err) = Config::load_lenient().unwrap();
+        assert_eq!(lenient, Config::default());
+        assert!(matches!(err, Some(AdventError::BadConfig { .. })));
+
+        let backup = Config::set_aside().unwrap();
+        assert_eq!(backup, dir.join("config.toml.bak"));
+        assert_eq!(fs::read_to_string(backup).unwrap(), "nope = 1\n");
+        assert!(Config::load_lenient().unwrap().1.is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_set() {
+        let mut config = Config::default();
+        assert!(config.set("nope", Some("1")).is_err());
+        assert!(config.set("throttle.sync", Some("soon")).is_err());
+        assert!(config.set("profile", Some("bad name")).is_err());
+
+        config
+            .set("base_url", Some("http://localhost:8000"))
+            .unwrap();
+        assert_eq!(
+            config.get("base_url").unwrap().as_deref(),
+            Some("http://localhost:8000")
+        );
+        config.set("base_url", None).unwrap();
+        assert_eq!(config.get("base_url").unwrap(), None);
+    }
+
+    #[test]
+    fn test_precedence() {
+        let (_guard, dir) = input_store::temp_config("config-precedence");
+
+        assert_eq!(input_store::init_profile(None).unwrap(), DEFAULT_PROFILE);
+        let (_, source) = Config::load()
+            .unwrap()
+            .effective("profile")
+            .unwrap()
+            .unwrap();
+        assert_eq!(source, Source::Default);
+
+        let mut config = Config::default();
+        config.set("profile", Some("alt")).unwrap();
+        config.save().unwrap();
+        assert_eq!(input_store::init_profile(None).unwrap(), "alt");
+        assert_eq!(input_store::active_profile(), "alt");
+        assert_eq!(
+            config.effective("profile").unwrap(),
+            Some(("alt".to_string(), Source::File))
+        );
+
+        env::set_var("AOC_PROFILE", "work");
+        assert_eq!(input_store::init_profile(None).unwrap(), "work");
+        assert_eq!(
+            config.effective("profile").unwrap(),
+            Some(("work".to_string(), Source::Env("AOC_PROFILE")))
+        );
+
+        assert_eq!(input_store::init_profile(Some("flag")).unwrap(), "flag");
+        assert_eq!(input_store::active_profile(), "flag");
+
+        // only read once, a config that breaks later doesn't change the pinned profile
+        env::remove_var("AOC_PROFILE");
+        fs::write(dir.join("config.toml"), "profile = [\n").unwrap();
+        assert_eq!(input_store::active_profile(), "flag");
+        assert!(input_store::init_profile(None).is_err());
+
+        input_store::set_profile(None).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::answer::{format_wait, Verdict};
+use crate::input_store;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// any request to the site
+    Request,
+    /// answer submissions only
+    Answer,
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scope::Request => write!(f, "request"),
+            Scope::Answer => write!(f, "answer"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cooldown {
+    pub until: u64,
+    pub reason: String,
+}
+
+#[derive(Debug)]
+pub struct Throttled {
+    pub scope: Scope,
+    pub remaining: Duration,
+    pub reason: String,
+}
+
+impl Display for Throttled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cooling down after {}: next {} allowed in {}",
+            self.reason,
+            self.scope,
+            format_wait(self.remaining)
+        )
+    }
+}
+
+impl std::error::Error for Throttled {}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Cooldowns {
+    pub scopes: BTreeMap<Scope, Cooldown>,
+}
+
+fn unix(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+impl Cooldowns {
+    pub fn load() -> Result<Self> {
+        let path = input_store::cooldown_file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&input_store::read_file(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = input_store::cooldown_file_path()?;
+        input_store::write_file(path, &serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// extends the cooldown for `scope` to at least `now + wait`
+    pub fn set(&mut self, scope: Scope, wait: Duration, reason: &str, now: SystemTime) {
+        let until = unix(now) + wait.as_secs();
+        let current = self.scopes.get(&scope).map(|c| c.until).unwrap_or(0);
+        if until > current {
+            self.scopes.insert(
+                scope,
+                Cooldown {
+                    until,
+                    reason: reason.to_string(),
+                },
+            );
+        }
+    }
+
+    /// how long until a request in `scope` is allowed. answers are also held back by request cooldowns.
+    pub fn remaining(&self, scope: Scope, now: SystemTime) -> Option<Throttled> {
+        let now = unix(now);
+        let applicable: &[Scope] = match scope {
+            Scope::Request => &[Scope::Request],
+            Scope::Answer => &[Scope::Request, Scope::Answer],
+        };
+
+        applicable
+            .iter()
+            .filter_map(|s| self.scopes.get(s).map(|c| (s, c)))
+            .filter(|(_, c)| c.until > now)
+            .max_by_key(|(_, c)| c.until)
+            .map(|(&scope, c)| Throttled {
+                scope,
+                remaining: Duration::from_secs(c.until - now),
+                reason: c.reason.clone(),
+            })
+    }
+}
+
+/// fails fast if `scope` is still cooling down
+pub fn check(scope: Scope) -> Result<()> {
+    match Cooldowns::load()?.remaining(scope, SystemTime::now()) {
+        Some(throttled) => Err(throttled.into()),
+        None => Ok(()),
+    }
+}
+
+pub fn record(scope: Scope, wait: Duration, reason: &str) -> Result<()> {
+    let mut cooldowns = Cooldowns::load()?;
+    cooldowns.set(scope, wait, reason, SystemTime::now());
+    cooldowns.save()
+}
+
+/// the cooldown the site asked for in an answer response, if any
+pub fn from_verdict(verdict: &Verdict) -> Option<Duration> {
+    match verdict {
+        Verdict::RateLimited { wait } => Some(*wait),
+        Verdict::Wrong { wait, .. } => *wait,
+        _ => None,
+    }
+}
+
+/// the remaining cooldown if `err` was caused by one
+pub fn throttled(err: &anyhow::Error) -> Option<&Throttled> {
+    err.downcast_ref::<Throttled>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_remaining() {
+        let mut cooldowns = Cooldowns::default();
+        cooldowns.set(
+            Scope::Answer,
+            Duration::from_secs(60),
+            "wrong answer",
+            at(100),
+        );
+
+        assert!(cooldowns.remaining(Scope::Request, at(100)).is_none());
+
+        let throttled = cooldowns.remaining(Scope::Answer, at(130)).unwrap();
+        assert_eq!(throttled.scope, Scope::Answer);
+        assert_eq!(throttled.remaining, Duration::from_secs(30));
+
+        assert!(cooldowns.remaining(Scope::Answer, at(160)).is_none());
+    }
+
+    #[test]
+    fn test_request_cooldown_blocks_answers() {
+        let mut cooldowns = Cooldowns::default();
+        cooldowns.set(
+            Scope::Answer,
+            Duration::from_secs(10),
+            "wrong answer",
+            at(0),
+        );
+        cooldowns.set(Scope::Request, Duration::from_secs(300), "429", at(0));
+
+        let throttled = cooldowns.remaining(Scope::Answer, at(5)).unwrap();
+        assert_eq!(throttled.scope, Scope::Request);
+        assert_eq!(throttled.remaining, Duration::from_secs(295));
+    }
+
+    #[test]
+    fn test_set_only_extends() {
+        let mut cooldowns = Cooldowns::default();
+        cooldowns.set(Scope::Request, Duration::from_secs(300), "429", at(0));
+        cooldowns.set(Scope::Request, Duration::from_secs(30), "503", at(10));
+
+        let throttled = cooldowns.remaining(Scope::Request, at(10)).unwrap();
+        assert_eq!(throttled.reason, "429");
+        assert_eq!(throttled.remaining, Duration::from_secs(290));
+    }
+
+    #[test]
+    fn test_from_verdict() {
+        let verdict = Verdict::from_html(include_str!("../fixtures/answer/rate_limited.html"));
+        assert_eq!(from_verdict(&verdict), Some(Duration::from_secs(34)));
+        assert_eq!(from_verdict(&Verdict::Correct), None);
+    }
+}
@@ -0,0 +1,109 @@
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::answer::format_wait;
+use crate::calendar;
+
+#[derive(Debug, Error)]
+pub enum AdventError {
+    #[error("can't find a home directory to keep inputs and cookies in")]
+    MissingConfig,
+
+    #[error("no cookie for profile {profile} at {}", path.display())]
+    MissingCookie { profile: String, path: PathBuf },
+
+    #[error("the cookie isn't a valid header value")]
+    InvalidCookie,
+
+    #[error("no cached input for {year}-{day:02} at {}", path.display())]
+    MissingInput {
+        year: usize,
+        day: usize,
+        path: PathBuf,
+    },
+
+    #[error("the cached input for {year}-{day:02} at {} is broken, {problem}", path.display())]
+    BrokenInput {
+        year: usize,
+        day: usize,
+        path: PathBuf,
+        problem: String,
+    },
+
+    #[error("there's no {year}-{day:02}, {}", describe(*year))]
+    NoSuchDay { year: usize, day: usize },
+
+    #[error("{year}-{day:02} isn't unlocked yet, {} to go", format_wait(*remaining))]
+    NotUnlocked {
+        year: usize,
+        day: usize,
+        remaining: Duration,
+    },
+
+    #[error("{url} answered {status}")]
+    Http { status: u16, url: String },
+
+    #[error("the site says we're not logged in, the cookie expired or isn't set")]
+    LoggedOut,
+
+    #[error("{}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("there's no {key:?} setting")]
+    UnknownConfigKey { key: String },
+
+    #[error("can't read {}: {reason}", path.display())]
+    BadConfig { path: PathBuf, reason: String },
+}
+
+fn describe(year: usize) -> String {
+    match calendar::days_in(year) {
+        Some(days) => format!("{} has {} days", year, days),
+        None if year < calendar::FIRST_YEAR => {
+            format!("the first event was in {}", calendar::FIRST_YEAR)
+        }
+        None => format!(
+            "the next event is in {}",
+            *calendar::event_years().end() + 1
+        ),
+    }
+}
+
+impl AdventError {
+    pub(crate) fn io(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| AdventError::Io { path, source }
+    }
+}
+
+/// the `AdventError` behind an `anyhow::Error`, if there is one
+pub fn advent_error(err: &anyhow::Error) -> Option<&AdventError> {
+    err.downcast_ref::<AdventError>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = AdventError::NotUnlocked {
+            year: 2022,
+            day: 3,
+            remaining: Duration::from_secs(90 * 60),
+        };
+        assert_eq!(err.to_string(), "2022-03 isn't unlocked yet, 1h 30m to go");
+
+        let err = AdventError::NoSuchDay {
+            year: 2025,
+            day: 13,
+        };
+        assert_eq!(err.to_string(), "there's no 2025-13, 2025 has 12 days");
+
+        let err: anyhow::Error = AdventError::LoggedOut.into();
+        assert!(matches!(advent_error(&err), Some(AdventError::LoggedOut)));
+    }
+}
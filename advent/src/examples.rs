@@ -0,0 +1,358 @@
+use std::fs::{self, DirBuilder};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::article;
+use crate::html::Node;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// 1-based position of the block on the page
+    pub block: usize,
+    pub part: u16,
+    pub text: String,
+    /// the example's answers as (part, answer), from the highlighted values after the block
+    pub answers: Vec<(u16, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub number: usize,
+    pub input: String,
+    /// expected answers as (part, answer)
+    pub answers: Vec<(u16, String)>,
+}
+
+struct Highlight {
+    value: String,
+    /// said to be the answer, eg. "the answer is <em>7</em>"
+    stated: bool,
+}
+
+enum Event {
+    Block(String),
+    Highlight(Highlight),
+}
+
+// the prose since the last sentence ended
+#[derive(Default)]
+struct Sentence {
+    text: String,
+    highlights: usize,
+}
+
+impl Sentence {
+    fn push(&mut self, text: &str) {
+        self.text.push_str(text);
+        let end = self
+            .text
+            .rmatch_indices(['.', '?', '!'])
+            .map(|(at, _)| at + 1)
+            .find(|&at| {
+                self.text[at..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace)
+            });
+        if let Some(at) = end {
+            self.text = self.text[at..].to_string();
+            self.highlights = 0;
+        }
+    }
+
+    // only the first highlight in a sentence about the answer, not "the answer is 7 for the 2nd elf"
+    fn states_answer(&self) -> bool {
+        self.highlights == 0 && self.text.to_lowercase().contains("answer")
+    }
+}
+
+// highlighted values are `<code><em>7</em></code>` or `<em><code>7</code></em>`
+fn is_highlight(node: &Node) -> bool {
+    match node.name() {
+        Some("code") => node.children().iter().any(|c| c.is("em")),
+        Some("em") => node.children().iter().any(|c| c.is("code")),
+        _ => false,
+    }
+}
+
+fn events(node: &Node, sentence: &mut Sentence, out: &mut Vec<Event>) {
+    if let Node::Text(text) = node {
+        sentence.push(text);
+        return;
+    }
+    if node.is("pre") {
+        if let Some(code) = node.find_all("code").first() {
+            out.push(Event::Block(code.text()));
+        }
+        *sentence = Sentence::default();
+        return;
+    }
+    if is_highlight(node) {
+        let value = node.text().trim().to_string();
+        out.push(Event::Highlight(Highlight {
+            stated: sentence.states_answer(),
+            value: value.clone(),
+        }));
+        sentence.highlights += 1;
+        sentence.push(&value);
+        return;
+    }
+    if matches!(node.name(), Some("p" | "li" | "h2")) {
+        *sentence = Sentence::default();
+    }
+    for child in node.children() {
+        events(child, sentence, out);
+    }
+}
+
+// a stated answer beats any other highlight, otherwise the last one wins
+fn prefer(best: &mut Option<Highlight>, highlight: Highlight) {
+    if !best.as_ref().is_some_and(|b| b.stated) {
+        *best = Some(highlight);
+    }
+}
+
+fn settle(examples: &mut [Example], target: Option<usize>, part: u16, best: Option<Highlight>) {
+    if let (Some(example), Some(best)) = (target.and_then(|t| examples.get_mut(t)), best) {
+        example.answers.push((part, best.value));
+    }
+}
+
+pub fn extract(page: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+
+    for (idx, description) in article::descriptions(page).iter().enumerate() {
+        let part = idx as u16 + 1;
+        let mut evts = vec![];
+        events(description, &mut Sentence::default(), &mut evts);
+
+        // part 2 usually reuses part 1's last example without showing it again
+        let mut target = match evts.iter().any(|e| matches!(e, Event::Block(_))) {
+            true => None,
+            false => examples.len().checked_sub(1),
+        };
+        let mut best = None;
+        for event in evts {
+            match event {
+                Event::Block(text) => {
+                    settle(&mut examples, target, part, best.take());
+                    examples.push(Example {
+                        block: examples.len() + 1,
+                        part,
+                        text,
+                        answers: vec![],
+                    });
+                    target = Some(examples.len() - 1);
+                }
+                Event::Highlight(highlight) => prefer(&mut best, highlight),
+            }
+        }
+        settle(&mut examples, target, part, best);
+    }
+
+    examples
+}
+
+fn fixture_path(dir: &Path, number: usize, ext: &str) -> PathBuf {
+    dir.join(format!("example_{:02}.{}", number, ext))
+}
+
+pub fn read_fixtures(dir: &Path) -> Result<Vec<Fixture>> {
+    let mut fixtures = vec![];
+    if !dir.exists() {
+        return Ok(fixtures);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(number) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix("example_"))
+            .and_then(|n| n.strip_suffix(".txt"))
+            .and_then(|n| n.parse().ok())
+        else {
+            continue;
+        };
+
+        let answers_path = fixture_path(dir, number, "answers");
+        let answers = if answers_path.exists() {
+            parse_answers(&fs::read_to_string(answers_path)?)
+        } else {
+            vec![]
+        };
+
+        fixtures.push(Fixture {
+            number,
+            input: fs::read_to_string(&path)?,
+            answers,
+        });
+    }
+
+    fixtures.sort_by_key(|f| f.number);
+    Ok(fixtures)
+}
+
+/// what `write_fixture` did with an example
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Written {
+    New(usize),
+    /// the block was already this fixture, and answers it didn't have were added
+    Updated(usize),
+    Unchanged(usize),
+}
+
+/// writes `example` as the next free numbered fixture in `dir`, unless one already has its text
+pub fn write_fixture(dir: &Path, example: &Example) -> Result<Written> {
+    let fixtures = read_fixtures(dir)?;
+
+    if let Some(existing) = fixtures
+        .iter()
+        .find(|f| f.input.trim_end() == example.text.trim_end())
+    {
+        let missing: Vec<(u16, String)> = example
+            .answers
+            .iter()
+            .filter(|(part, _)| !existing.answers.iter().any(|(p, _)| p == part))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            return Ok(Written::Unchanged(existing.number));
+        }
+
+        // appended, so anything else in the file is kept
+        let path = fixture_path(dir, existing.number, "answers");
+        let mut answers = match path.exists() {
+            true => fs::read_to_string(&path)?,
+            false => String::new(),
+        };
+        if !answers.is_empty() && !answers.ends_with('\n') {
+            answers.push('\n');
+        }
+        answers.push_str(&format_answers(&missing));
+        fs::write(path, answers)?;
+        return Ok(Written::Updated(existing.number));
+    }
+
+    let number = fixtures.last().map(|f| f.number + 1).unwrap_or(1);
+
+    DirBuilder::new().recursive(true).create(dir)?;
+    fs::write(fixture_path(dir, number, "txt"), &example.text)?;
+    if !example.answers.is_empty() {
+        fs::write(
+            fixture_path(dir, number, "answers"),
+            format_answers(&example.answers),
+        )?;
+    }
+
+    Ok(Written::New(number))
+}
+
+pub fn format_answers(answers: &[(u16, String)]) -> String {
+    answers
+        .iter()
+        .map(|(part, answer)| format!("part_{} => {}\n", part, answer))
+        .collect()
+}
+
+/// parses lines in the `part_1 => 42` format every day binary prints, or `part 1 => 42` like some older ones
+pub fn parse_answers(output: &str) -> Vec<(u16, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (part, answer) = line.split_once("=>")?;
+            let part = part
+                .trim()
+                .strip_prefix("part")?
+                .trim_start_matches(['_', ' '])
+                .parse()
+                .ok()?;
+            Some((part, answer.trim().to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let page = include_str!("../fixtures/puzzle/both_parts.html");
+        let examples = extract(page);
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    block: 1,
+                    part: 1,
+                    text: "199\n200\n208\n".to_string(),
+                    answers: vec![(1, "2".to_string())],
+                },
+                Example {
+                    block: 2,
+                    part: 2,
+                    text: "607\n618\n".to_string(),
+                    answers: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_prefers_stated_answers() {
+        let page = include_str!("../fixtures/puzzle/reused_example.html");
+        let examples = extract(page);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(
+            examples[0].answers,
+            vec![(1, "11000".to_string()), (2, "18000".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_write_fixture() {
+        let dir = std::env::temp_dir().join(format!("advent-fixtures-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut example = Example {
+            block: 1,
+            part: 1,
+            text: "1000\n2000\n".to_string(),
+            answers: vec![(1, "3000".to_string())],
+        };
+
+        assert_eq!(write_fixture(&dir, &example).unwrap(), Written::New(1));
+        assert_eq!(
+            write_fixture(&dir, &example).unwrap(),
+            Written::Unchanged(1)
+        );
+
+        // part 2 unlocked since
+        example.answers.push((2, "1000".to_string()));
+        assert_eq!(write_fixture(&dir, &example).unwrap(), Written::Updated(1));
+
+        example.text = "4000\n".to_string();
+        assert_eq!(write_fixture(&dir, &example).unwrap(), Written::New(2));
+
+        let fixtures = read_fixtures(&dir).unwrap();
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(
+            fixtures[0].answers,
+            vec![(1, "3000".to_string()), (2, "1000".to_string())]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let output = "some debug line\npart_1 => 42\npart_2 => not done\n";
+        assert_eq!(
+            parse_answers(output),
+            vec![(1, "42".to_string()), (2, "not done".to_string())]
+        );
+    }
+}
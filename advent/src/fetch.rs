@@ -1,104 +1,309 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{DirBuilder, File};
 use std::io::{BufReader, Read, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 use std::{env, fs};
 
-use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
 use reqwest::{self, StatusCode};
 
 use anyhow::{Error, Result};
 
-use crate::input_store;
-//use scraper::{Html, Selector};
+use crate::answer::Verdict;
+use crate::article::{self, Style};
+use crate::calendar;
+use crate::config::{Config, DEFAULT_USER_AGENT};
+use crate::cooldown::{self, Scope, Throttled};
+use crate::error::AdventError;
+use crate::input_store::{self, Selector};
+use crate::leaderboard::Leaderboard;
+use crate::ledger::Ledger;
+use crate::session::{self, User};
+use crate::source::{Cached, DirectorySource, InputSource};
+use crate::status;
 
-fn make_client() -> Client {
-    let mut headers = HeaderMap::default();
+pub use crate::config::DEFAULT_BASE_URL;
+const MIRROR_KEY: &str = "AOC_MIRROR";
 
-    let cookie = crate::input_store::get_cookie();
-    let val = HeaderValue::from_str(cookie.trim()).unwrap();
+// used when a 429 doesn't say how long to back off for
+const TOO_MANY_REQUESTS_BACKOFF: Duration = Duration::from_secs(5 * 60);
+const SERVER_ERROR_BACKOFF: Duration = Duration::from_secs(30);
 
-    headers.insert("Cookie", val);
+// the site asks that the leaderboard api isn't polled more than once every 15 minutes
+pub const LEADERBOARD_TTL: Duration = Duration::from_secs(15 * 60);
 
-    let client = Client::builder().default_headers(headers).build().unwrap();
-    client
-}
+// part 1 can be solved on the site without us knowing, so a page without part 2 is only kept
+// this long
+pub const PUZZLE_TTL: Duration = Duration::from_secs(15 * 60);
 
-fn fetch_input(selector: &input_store::Selector) -> Result<String> {
-    let client = make_client();
-    let url = format!(
-        "https://adventofcode.com/{}/day/{}/input",
-        selector.year, selector.day
-    );
-    let resp = client.get(url).send()?;
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
 
-    match resp.status() {
-        StatusCode::OK => Ok(resp.text()?),
+// records a cooldown for throttling or server errors before turning the status into an error
+fn check_status(resp: Response) -> Result<Response> {
+    let status = resp.status();
+    let backoff = match status {
+        StatusCode::OK => return Ok(resp),
+        StatusCode::TOO_MANY_REQUESTS => retry_after(&resp).unwrap_or(TOO_MANY_REQUESTS_BACKOFF),
+        s if s.is_server_error() => retry_after(&resp).unwrap_or(SERVER_ERROR_BACKOFF),
         _ => {
-            let message = format!("resp: {}", resp.status());
-            Err(Error::msg(message))
+            let url = resp.url().to_string();
+            // an expired or missing session gets a 400 with a "please log in" body
+            if session::is_logged_out(&resp.text().unwrap_or_default()) {
+                return Err(AdventError::LoggedOut.into());
+            }
+            return Err(AdventError::Http {
+                status: status.as_u16(),
+                url,
+            }
+            .into());
         }
+    };
+
+    let reason = format!("resp: {}", status);
+    cooldown::record(Scope::Request, backoff, &reason)?;
+    Err(Throttled {
+        scope: Scope::Request,
+        remaining: backoff,
+        reason,
     }
+    .into())
+}
+
+/// adventofcode.com, or anything serving the same paths
+pub struct Site {
+    base_url: String,
+    client: Client,
 }
 
-pub fn submit_answer(
-    selector: input_store::Selector,
-    level: u16,
-    answer: String,
-) -> Result<String> {
-    let client = make_client();
-    let url = format!(
-        "https://adventofcode.com/{}/day/{}/answer",
-        selector.year, selector.day
-    );
+impl Site {
+    pub fn new(base_url: &str, cookie: Option<&str>) -> Result<Self> {
+        Self::with_user_agent(base_url, cookie, DEFAULT_USER_AGENT)
+    }
+
+    pub fn with_user_agent(base_url: &str, cookie: Option<&str>, user_agent: &str) -> Result<Self> {
+        let mut headers = HeaderMap::default();
+        if let Some(cookie) = cookie {
+            let value =
+                HeaderValue::from_str(cookie.trim()).map_err(|_| AdventError::InvalidCookie)?;
+            headers.insert("Cookie", value);
+        }
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .user_agent(user_agent)
+            .build()?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        })
+    }
+
+    /// the site at `base_url` with the stored cookie, if there is one, and the configured user agent
+    pub fn from_store(base_url: &str) -> Result<Self> {
+        let cookie = match input_store::has_cookie() {
+            true => Some(input_store::get_cookie()?),
+            false => None,
+        };
+        let config = Config::load()?;
+        Self::with_user_agent(base_url, cookie.as_deref(), &config.user_agent())
+    }
+
+    /// the site at `$AOC_BASE_URL`, config.toml's `base_url`, or adventofcode.com
+    pub fn from_env() -> Result<Self> {
+        Self::from_store(&Config::load()?.base_url()?)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn get(&self, path: &str) -> Result<String> {
+        cooldown::check(Scope::Request)?;
+
+        let url = format!("{}{}", self.base_url, path);
+        let resp = check_status(self.client.get(url).send()?)?;
+        let body = resp.text()?;
+
+        if session::is_logged_out(&body) {
+            return Err(AdventError::LoggedOut.into());
+        }
+
+        Ok(body)
+    }
 
-    let mut params = HashMap::new();
-    params.insert("level", level.to_string());
-    params.insert("answer", answer);
+    /// checks the session against the site
+    pub fn whoami(&self) -> Result<User> {
+        let page = self.get("/")?;
+        session::user(&page).ok_or_else(|| AdventError::LoggedOut.into())
+    }
 
-    dbg!(&params);
+    pub fn fetch_puzzle_page(&self, selector: &Selector) -> Result<String> {
+        calendar::check_unlocked(selector.year(), selector.day())?;
+        self.get(&format!("/{}/day/{}", selector.year(), selector.day()))
+    }
 
-    let resp = client.post(url).form(&params).send()?;
+    pub fn get_or_fetch_puzzle(&self, selector: &Selector, force: bool) -> Result<String> {
+        let filename = selector.puzzle_filename()?;
+        if !force && filename.exists() {
+            let page = input_store::read_puzzle_page(selector)?;
+            let age = fs::metadata(&filename)?
+                .modified()?
+                .elapsed()
+                .unwrap_or_default();
+            if !page_is_stale(selector, &page, age)? {
+                Ledger::learn(selector, &article::answers(&page))?;
+                return Ok(page);
+            }
+        }
 
-    dbg!(&resp);
+        let page = self.fetch_puzzle_page(selector)?;
+        let description = article::render_page(&page, Style::Markdown);
+        input_store::write_puzzle_page(selector, &page, &description)?;
+        Ledger::learn(selector, &article::answers(&page))?;
 
-    Ok(resp.text()?)
+        Ok(page)
+    }
+
+    pub fn fetch_leaderboard(&self, year: usize, id: u64) -> Result<String> {
+        self.get(&format!("/{}/leaderboard/private/view/{}.json", year, id))
+    }
+
+    /// the leaderboard and how old it is. the cached copy is used until it's `LEADERBOARD_TTL` old.
+    pub fn get_or_fetch_leaderboard(
+        &self,
+        year: usize,
+        id: u64,
+    ) -> Result<(Leaderboard, Duration)> {
+        let filename = input_store::leaderboard_filename(year, id)?;
+        let (payload, age) = fresh_or_fetch(filename, LEADERBOARD_TTL, || {
+            let payload = self.fetch_leaderboard(year, id)?;
+            Leaderboard::parse(&payload)?;
+            Ok(payload)
+        })?;
+
+        Ok((Leaderboard::parse(&payload)?, age))
+    }
+
+    pub fn fetch_calendar(&self, year: usize) -> Result<String> {
+        self.get(&format!("/{}", year))
+    }
+
+    /// stars per day on the event's calendar page, cached like the leaderboard
+    pub fn get_or_fetch_stars(&self, year: usize) -> Result<(BTreeMap<usize, u8>, Duration)> {
+        let filename = input_store::calendar_filename(year)?;
+        let (page, age) = fresh_or_fetch(filename, LEADERBOARD_TTL, || self.fetch_calendar(year))?;
+
+        Ok((status::parse_stars(&page), age))
+    }
+
+    pub fn submit_answer(&self, selector: &Selector, level: u16, answer: &str) -> Result<Verdict> {
+        cooldown::check(Scope::Answer)?;
+
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url,
+            selector.year(),
+            selector.day()
+        );
+
+        let mut params = HashMap::new();
+        params.insert("level", level.to_string());
+        params.insert("answer", answer.to_string());
+
+        let resp = check_status(self.client.post(url).form(&params).send()?)?;
+        let body = resp.text()?;
+        if session::is_logged_out(&body) {
+            return Err(AdventError::LoggedOut.into());
+        }
+        let verdict = Verdict::from_html(&body);
 
-    // let document = Html::parse_document(resp.text().unwrap());
-    // let selector = Selector::parse("article").unwrap();
-    // let selected = document.select(&selector).next().unwrap();
-    // selected.inner_html()
+        if let Some(wait) = cooldown::from_verdict(&verdict) {
+            cooldown::record(Scope::Answer, wait, "answer submission")?;
+        }
+
+        Ok(verdict)
+    }
 }
 
-pub fn get_or_fetch_input(selector: &input_store::Selector, force: bool) -> Result<String> {
-    if !force && selector.exists() {
-        return input_store::read_puzzle_input(&selector);
+impl InputSource for Site {
+    fn fetch_input(&self, selector: &Selector) -> Result<String> {
+        calendar::check_unlocked(selector.year(), selector.day())?;
+        self.get(&format!(
+            "/{}/day/{}/input",
+            selector.year(),
+            selector.day()
+        ))
     }
+}
 
-    let value = fetch_input(&selector)?;
-    input_store::write_puzzle_input(selector, value.clone())?;
+// the cached copy at `filename` and its age if it's younger than `ttl`, otherwise a fresh one
+fn fresh_or_fetch(
+    filename: PathBuf,
+    ttl: Duration,
+    fetch: impl FnOnce() -> Result<String>,
+) -> Result<(String, Duration)> {
+    if filename.exists() {
+        let age = fs::metadata(&filename)?
+            .modified()?
+            .elapsed()
+            .unwrap_or_default();
+        if age < ttl {
+            return Ok((input_store::read_file(filename)?, age));
+        }
+    }
 
-    Ok(value)
+    let payload = fetch()?;
+    input_store::write_file(filename, &payload)?;
+    Ok((payload, Duration::ZERO))
 }
 
-pub fn get_all_inputs(year: usize, force: bool) -> Result<()> {
-    for day in 1..=25 {
-        let selector = input_store::Selector { year, day };
-        get_or_fetch_input(&selector, force)?;
+// a cached page only has part 2 if it was fetched after part 1 was solved, so it's stale once
+// a part was solved since (here, or for a page without part 2, maybe on the site)
+fn page_is_stale(selector: &Selector, page: &str, age: Duration) -> Result<bool> {
+    let parts = article::descriptions(page).len();
+    let solved = Ledger::load(selector)?.solutions().len();
+    Ok((parts < 2 && (solved > 0 || age >= PUZZLE_TTL)) || article::answers(page).len() < solved)
+}
+
+/// the site from the environment, or the directory at `$AOC_MIRROR` if it's set
+pub fn source_from_env() -> Result<Box<dyn InputSource>> {
+    match env::var(MIRROR_KEY) {
+        Ok(dir) => Ok(Box::new(DirectorySource::new(dir))),
+        Err(_) => Ok(Box::new(Site::from_env()?)),
     }
+}
 
-    Ok(())
+pub fn submit_answer(selector: &Selector, level: u16, answer: &str) -> Result<Verdict> {
+    Site::from_env()?.submit_answer(selector, level, answer)
+}
+
+pub fn get_or_fetch_input(selector: &Selector, force: bool) -> Result<String> {
+    Cached::new(source_from_env()?).get(selector, force)
+}
+
+pub fn get_all_inputs(year: usize, force: bool) -> Result<()> {
+    Cached::new(source_from_env()?).get_all(year, force)
+}
+
+pub fn try_get_input(year: usize, day: usize) -> Result<String> {
+    let selector = Selector::new(year, day)?;
+    Ok(get_or_fetch_input(&selector, false)?.trim().to_string())
 }
 
 pub fn get_input(year: usize, day: usize) -> String {
-    let selector = input_store::Selector { year, day };
-    let puzzle_input = get_or_fetch_input(&selector, false)
-        .unwrap()
-        .trim()
-        .to_string();
-    puzzle_input
+    try_get_input(year, day).unwrap_or_else(|e| panic!("{:#}", e))
 }
 
 #[cfg(test)]
@@ -106,9 +311,105 @@ mod tests {
 
     use super::*;
     use std::env;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
 
     #[test]
     fn do_thing() {
-        assert!(get_input(2020, 1).len() > 0);
+        assert!(!get_input(2020, 1).is_empty());
+    }
+
+    // serves a single response and hands back the request head it got
+    fn stand_in(body: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        stand_in_with("200 OK", body)
+    }
+
+    fn stand_in_with(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let head: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map(|l| l.unwrap())
+                .take_while(|l| !l.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_site_against_stand_in() {
+        // requests check the cooldown, which is kept in $AOC_CONFIG
+        let (_guard, dir) = input_store::temp_config("fetch-stand-in");
+        let (base_url, handle) = stand_in("1\n2\n3\n");
+        let site = Site::new(&format!("{}/", base_url), Some("session=abc")).unwrap();
+
+        let input = site.fetch_input(&Selector::new(2022, 1).unwrap()).unwrap();
+        assert_eq!(input, "1\n2\n3\n");
+
+        let head = handle.join().unwrap();
+        assert_eq!(head[0], "GET /2022/day/1/input HTTP/1.1");
+        assert!(head
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("cookie: session=abc")));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_logged_out_input() {
+        let (_guard, dir) = input_store::temp_config("fetch-logged-out");
+        let (base_url, handle) = stand_in_with(
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        );
+        let site = Site::new(&base_url, Some("session=expired")).unwrap();
+
+        let err = site
+            .fetch_input(&Selector::new(2022, 1).unwrap())
+            .unwrap_err();
+        assert!(session::logged_out(&err));
+        handle.join().unwrap();
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_page_is_stale() {
+        let (_guard, dir) = input_store::temp_config("fetch-stale-page");
+        let selector = Selector::new(2022, 1).unwrap();
+        let part_1 = include_str!("../fixtures/puzzle/part_1.html");
+        let both = include_str!("../fixtures/puzzle/both_parts.html");
+        let old = PUZZLE_TTL + Duration::from_secs(1);
+
+        assert!(!page_is_stale(&selector, part_1, Duration::ZERO).unwrap());
+        assert!(page_is_stale(&selector, part_1, old).unwrap());
+        assert!(!page_is_stale(&selector, both, old).unwrap());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_whoami() {
+        let (_guard, dir) = input_store::temp_config("fetch-whoami");
+        let (base_url, handle) = stand_in(include_str!("../fixtures/session/logged_in.html"));
+        let site = Site::new(&base_url, Some("session=abc")).unwrap();
+
+        assert_eq!(site.whoami().unwrap().name, "kryptn");
+        assert_eq!(handle.join().unwrap()[0], "GET / HTTP/1.1");
+        let _ = fs::remove_dir_all(dir);
     }
 }
@@ -203,13 +203,13 @@ impl Coordinate {
         match dir {
             RelativeDirection::Right => Self {
                 x: self.y,
-                y: self.x * -1,
+                y: -self.x,
             },
             RelativeDirection::Left => Self {
-                x: self.y * -1,
+                x: -self.y,
                 y: self.x,
             },
-            RelativeDirection::Up => self.clone(),
+            RelativeDirection::Up => *self,
             RelativeDirection::Down => self.scale(-1),
         }
     }
@@ -222,7 +222,7 @@ impl Coordinate {
     }
 
     pub fn ring(&self, distance: usize) -> Vec<Coordinate> {
-        let mut out = vec![self.clone()];
+        let mut out = vec![*self];
         out.extend(self.cardinals());
         let mut dia = 1;
 
@@ -475,7 +475,7 @@ pub fn print_grid<T>(g: &Grid<T>)
 where
     T: Default + std::fmt::Display + Clone,
 {
-    let (lower, upper) = full_bounding_box(&g);
+    let (lower, upper) = full_bounding_box(g);
 
     for row in iter_rows(lower, upper) {
         for coord in row {
@@ -485,9 +485,9 @@ where
             };
             print!("{}", item);
         }
-        print!("\n");
+        println!();
     }
-    println!("");
+    println!();
 }
 
 pub trait Passable {
@@ -513,7 +513,7 @@ fn inner_traverse_astar<T: Passable>(
     }
 
     let sorted_cardinals = position.weighted_cardinals(|c| {
-        let tc = c.clone();
+        let tc = *c;
         manhattan(tc, goal)
     });
 
@@ -530,16 +530,15 @@ fn inner_traverse_astar<T: Passable>(
 
     let results: Vec<Traversal> = valid_coordinates
         .cloned()
-        .map(|c| {
+        .flat_map(|c| {
             let mut visited = visited.clone();
             visited.push(c);
 
             inner_traverse_astar(grid, c, goal, visited)
         })
-        .flatten()
         .collect();
 
-    if results.len() == 0 {
+    if results.is_empty() {
         vec![Traversal::NoPath(visited)]
     } else {
         results
@@ -560,7 +559,7 @@ pub fn shortest_path<T: Passable>(
     start: Coordinate,
     goal: Coordinate,
 ) -> Option<Vec<Coordinate>> {
-    let paths = traverse_astar(&grid, start, goal);
+    let paths = traverse_astar(grid, start, goal);
     let sorted_paths: Vec<Vec<Coordinate>> = paths
         .iter()
         .filter(|&p| matches!(p, Traversal::Found(_)))
@@ -572,7 +571,7 @@ pub fn shortest_path<T: Passable>(
         .collect();
 
     if !sorted_paths.is_empty() {
-        Some(sorted_paths.iter().nth(0).unwrap().clone())
+        Some(sorted_paths.first().unwrap().clone())
     } else {
         None
     }
@@ -700,7 +699,7 @@ mod test {
     #[test]
     fn test_weighted_coordinate() {
         let anchor = Coordinate::zero();
-        let dirs = Coordinate::new(12, -5).weighted_cardinals(|c| manhattan(anchor, c.clone()));
+        let dirs = Coordinate::new(12, -5).weighted_cardinals(|c| manhattan(anchor, *c));
 
         println!("{:?}", dirs);
     }
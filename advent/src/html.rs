@@ -0,0 +1,339 @@
+// just enough html handling for the pages adventofcode.com serves. not a general parser.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    Element {
+        name: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "wbr",
+];
+
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+impl Node {
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Node::Element { name, .. } => Some(name),
+            Node::Text(_) => None,
+        }
+    }
+
+    pub fn is(&self, tag: &str) -> bool {
+        self.name() == Some(tag)
+    }
+
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .map(|c| c.split_whitespace().any(|c| c == class))
+            .unwrap_or(false)
+    }
+
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            Node::Text(_) => &[],
+        }
+    }
+
+    /// all of the text below this node, concatenated
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        self.push_text(&mut out);
+        out
+    }
+
+    fn push_text(&self, out: &mut String) {
+        match self {
+            Node::Text(t) => out.push_str(t),
+            Node::Element { children, .. } => children.iter().for_each(|c| c.push_text(out)),
+        }
+    }
+
+    pub fn find_all<'a>(&'a self, tag: &str) -> Vec<&'a Node> {
+        let mut out = vec![];
+        self.collect(&|n| n.is(tag), &mut out);
+        out
+    }
+
+    fn collect<'a>(&'a self, pred: &dyn Fn(&Node) -> bool, out: &mut Vec<&'a Node>) {
+        if pred(self) {
+            out.push(self);
+        }
+        for child in self.children() {
+            child.collect(pred, out);
+        }
+    }
+}
+
+pub fn find_all<'a>(nodes: &'a [Node], tag: &str) -> Vec<&'a Node> {
+    nodes.iter().flat_map(|n| n.find_all(tag)).collect()
+}
+
+pub fn find_where(nodes: &[Node], pred: impl Fn(&Node) -> bool) -> Vec<&Node> {
+    let mut out = vec![];
+    for node in nodes {
+        node.collect(&pred, &mut out);
+    }
+    out
+}
+
+pub fn decode_entities(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(idx) = rest.find('&') {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                    u32::from_str_radix(&entity[2..], 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                }
+                _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn parse_attrs(input: &str) -> Vec<(String, String)> {
+    let mut attrs = vec![];
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, remaining) = match after.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let end = after[1..].find(q).map(|e| e + 1).unwrap_or(after.len());
+                    (&after[1..end], after.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            rest = remaining.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+
+    attrs
+}
+
+struct Open {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+fn close(stack: &mut Vec<Open>) {
+    let open = stack.pop().unwrap();
+    let node = Node::Element {
+        name: open.name,
+        attrs: open.attrs,
+        children: open.children,
+    };
+    stack.last_mut().unwrap().children.push(node);
+}
+
+pub fn parse(input: &str) -> Vec<Node> {
+    let mut stack = vec![Open {
+        name: String::new(),
+        attrs: vec![],
+        children: vec![],
+    }];
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map(|end| &comment[end + 3..])
+                .unwrap_or("");
+            continue;
+        }
+
+        let Some(gt) = rest.find('>') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        let tag = &rest[1..gt];
+        rest = &rest[gt + 1..];
+
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_lowercase();
+            if stack.iter().skip(1).any(|o| o.name == name) {
+                while stack.last().unwrap().name != name {
+                    close(&mut stack);
+                }
+                close(&mut stack);
+            }
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_lowercase();
+        let attrs = parse_attrs(&tag[name_end..]);
+
+        if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+            let closing = format!("</{}", name);
+            let end = rest
+                .to_ascii_lowercase()
+                .find(&closing)
+                .unwrap_or(rest.len());
+            let children = vec![Node::Text(rest[..end].to_string())];
+            rest = rest[end..]
+                .find('>')
+                .map(|e| &rest[end + e + 1..])
+                .unwrap_or("");
+            stack.last_mut().unwrap().children.push(Node::Element {
+                name,
+                attrs,
+                children,
+            });
+            continue;
+        }
+
+        stack.push(Open {
+            name,
+            attrs,
+            children: vec![],
+        });
+        if self_closing || VOID_ELEMENTS.contains(&stack.last().unwrap().name.as_str()) {
+            close(&mut stack);
+        }
+    }
+
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+
+    stack.pop().unwrap().children
+}
+
+fn push_text(stack: &mut [Open], text: &str) {
+    if !text.is_empty() {
+        let decoded = decode_entities(text);
+        stack.last_mut().unwrap().children.push(Node::Text(decoded));
+    }
+}
+
+/// every `<article>` element on a page, in document order
+pub fn articles(input: &str) -> Vec<Node> {
+    find_all(&parse(input), "article")
+        .into_iter()
+        .cloned()
+        .collect()
+}
+
+/// whitespace-normalized text of every `<article>` on a page
+pub fn article_text(input: &str) -> String {
+    articles(input)
+        .iter()
+        .map(|a| a.text().split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("a &lt; b", "a < b")]
+    #[case("&amp;&amp;", "&&")]
+    #[case("&#65;&#x42;", "AB")]
+    #[case("fish & chips", "fish & chips")]
+    #[case("&unknown;", "&unknown;")]
+    fn test_decode_entities(#[case] given: &str, #[case] expected: &str) {
+        assert_eq!(decode_entities(given), expected);
+    }
+
+    #[test]
+    fn test_parse_nested() {
+        let nodes = parse(
+            r#"<!DOCTYPE html><main><article class="day-desc"><p>Hi <em title="x">there</em><br/>you</p></article></main>"#,
+        );
+        let articles = find_all(&nodes, "article");
+        assert_eq!(articles.len(), 1);
+        assert!(articles[0].has_class("day-desc"));
+        assert_eq!(articles[0].text(), "Hi thereyou");
+        assert_eq!(articles[0].find_all("em")[0].attr("title"), Some("x"));
+    }
+
+    #[test]
+    fn test_parse_unclosed_and_raw() {
+        let nodes = parse("<script>if (a < b) {}</script><p>one<p>two</div>");
+        assert_eq!(nodes[0].text(), "if (a < b) {}");
+        assert_eq!(find_all(&nodes, "p").len(), 2);
+    }
+}
@@ -3,100 +3,453 @@ use std::fs::{DirBuilder, File};
 use std::io::{BufReader, Read, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::RwLock;
 use std::{env, fs};
 
 use anyhow::{Error, Result};
 
+use crate::calendar;
+use crate::config::Config;
+use crate::error::AdventError;
+use crate::integrity::{self, InputMeta, Problem};
+
 const AOC_CONFIG_DIR_KEY: &str = "AOC_CONFIG";
+const AOC_PROFILE_KEY: &str = "AOC_PROFILE";
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+static PROFILE: RwLock<Option<String>> = RwLock::new(None);
 
-fn aoc_config_dir() -> PathBuf {
+pub(crate) fn aoc_config_dir() -> Result<PathBuf, AdventError> {
     match env::var(AOC_CONFIG_DIR_KEY) {
-        Ok(p) => PathBuf::from(p),
+        Ok(p) => Ok(PathBuf::from(p)),
         Err(_) => {
-            let mut home = dirs::home_dir().unwrap();
+            let mut home = dirs::home_dir().ok_or(AdventError::MissingConfig)?;
             home.push(".advent_of_code");
-            home
+            Ok(home)
         }
     }
 }
 
-fn input_cache_dir() -> PathBuf {
-    let mut p = aoc_config_dir();
+pub(crate) fn validate_profile(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::msg(format!(
+            "invalid profile name {:?}, use letters, numbers, - and _",
+            name
+        )))
+    }
+}
+
+/// selects the profile used by everything in this process. `None` goes back to `$AOC_PROFILE`.
+pub fn set_profile(name: Option<&str>) -> Result<()> {
+    if let Some(name) = name {
+        validate_profile(name)?;
+    }
+    *PROFILE.write().unwrap() = name.map(str::to_string);
+    Ok(())
+}
+
+fn env_profile() -> Option<String> {
+    env::var(AOC_PROFILE_KEY).ok().filter(|p| !p.is_empty())
+}
+
+/// pins `flag`, or `$AOC_PROFILE`, or config.toml's `profile`, or the default one, for the rest of
+/// the process. config.toml is only read here, so call this once at startup.
+pub fn init_profile(flag: Option<&str>) -> Result<String> {
+    pin_profile(flag, || Ok(Config::load()?.profile))
+}
+
+/// like `init_profile`, but a config.toml that can't be parsed is left out, and returned
+pub fn init_profile_lenient(flag: Option<&str>) -> Result<(String, Option<AdventError>)> {
+    let mut broken = None;
+    let profile = pin_profile(flag, || {
+        let (config, err) = Config::load_lenient()?;
+        broken = err;
+        Ok(config.profile)
+    })?;
+    Ok((profile, broken))
+}
+
+fn pin_profile(
+    flag: Option<&str>,
+    configured: impl FnOnce() -> Result<Option<String>>,
+) -> Result<String> {
+    let profile = match flag.map(str::to_string).or_else(env_profile) {
+        Some(profile) => profile,
+        None => configured()?.unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+    };
+    set_profile(Some(&profile))?;
+    Ok(profile)
+}
+
+/// the pinned profile, or `$AOC_PROFILE`, or the default one when `init_profile` wasn't called
+pub fn active_profile() -> String {
+    let set = PROFILE.read().unwrap().clone();
+    set.or_else(env_profile)
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+// the default profile lives directly in the config dir, so existing setups keep working
+fn profile_dir(profile: &str) -> Result<PathBuf, AdventError> {
+    let mut p = aoc_config_dir()?;
+    if profile != DEFAULT_PROFILE {
+        p.push("profiles");
+        p.push(profile);
+    }
+    Ok(p)
+}
+
+pub(crate) fn active_profile_dir() -> Result<PathBuf, AdventError> {
+    profile_dir(&active_profile())
+}
+
+fn input_cache_dir_for(profile: &str) -> Result<PathBuf, AdventError> {
+    let mut p = profile_dir(profile)?;
     p.push("input");
-    p
+    Ok(p)
 }
 
-fn cookie_file_path() -> PathBuf {
-    let mut p = aoc_config_dir();
+pub(crate) fn input_cache_dir() -> Result<PathBuf, AdventError> {
+    input_cache_dir_for(&active_profile())
+}
+
+pub(crate) fn cookie_file_path() -> Result<PathBuf, AdventError> {
+    let mut p = active_profile_dir()?;
     p.push(".cookie");
-    p
+    Ok(p)
+}
+
+pub(crate) fn config_filename() -> Result<PathBuf, AdventError> {
+    let mut p = aoc_config_dir()?;
+    p.push("config.toml");
+    Ok(p)
+}
+
+pub(crate) fn cooldown_file_path() -> Result<PathBuf, AdventError> {
+    let mut p = active_profile_dir()?;
+    p.push("cooldown.json");
+    Ok(p)
+}
+
+pub(crate) fn calendar_filename(year: usize) -> Result<PathBuf, AdventError> {
+    let mut p = input_cache_dir()?;
+    p.push(year.to_string());
+    p.push("calendar.html");
+    Ok(p)
+}
+
+pub(crate) fn sync_progress_path() -> Result<PathBuf, AdventError> {
+    let mut p = active_profile_dir()?;
+    p.push("sync.json");
+    Ok(p)
 }
 
-#[derive(Clone)]
+pub(crate) fn bench_history_path() -> Result<PathBuf, AdventError> {
+    let mut p = active_profile_dir()?;
+    p.push("bench.jsonl");
+    Ok(p)
+}
+
+/// every profile with a cookie or cached inputs, starting with the default one
+pub fn profiles() -> Result<Vec<String>> {
+    let mut out = vec![];
+
+    let default = profile_dir(DEFAULT_PROFILE)?;
+    if default.join(".cookie").exists() || default.join("input").exists() {
+        out.push(DEFAULT_PROFILE.to_string());
+    }
+
+    let dir = aoc_config_dir()?.join("profiles");
+    if dir.exists() {
+        let mut named = vec![];
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                named.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        named.sort();
+        out.extend(named);
+    }
+
+    Ok(out)
+}
+
+pub fn leaderboard_filename(year: usize, id: u64) -> Result<PathBuf, AdventError> {
+    let mut p = active_profile_dir()?;
+    p.push("leaderboard");
+    p.push(year.to_string());
+    p.push(format!("{}.json", id));
+    Ok(p)
+}
+
+/// a day that's part of an event, only made through `new` or `days` so it always is
+#[derive(Clone, Debug)]
 pub struct Selector {
-    pub year: usize,
-    pub day: usize,
+    year: usize,
+    day: usize,
 }
 
+/// kept for older callers, panics on a day that isn't part of an event.
+/// `Selector::new` returns the error instead.
 impl From<(usize, usize)> for Selector {
     fn from(t: (usize, usize)) -> Self {
-        Self {
-            year: t.0,
-            day: t.1,
-        }
+        Self::new(t.0, t.1).unwrap_or_else(|err| panic!("{}, use Selector::new", err))
     }
 }
 
 impl Selector {
-    pub fn filename(&self) -> PathBuf {
-        let mut input_filename = input_cache_dir();
-        input_filename.push(self.year.to_string());
-        input_filename.push(format!("{:02}", self.day));
+    /// a day that's part of an event, whether or not it's unlocked yet
+    pub fn new(year: usize, day: usize) -> Result<Self, AdventError> {
+        calendar::validate(year, day)?;
+        Ok(Self { year, day })
+    }
+
+    /// every day of `year`'s event, none if there wasn't one
+    pub fn days(year: usize) -> impl Iterator<Item = Selector> {
+        calendar::days(year).map(move |day| Self { year, day })
+    }
+
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    pub fn day(&self) -> usize {
+        self.day
+    }
+
+    pub fn dir(&self) -> Result<PathBuf, AdventError> {
+        let mut dir = input_cache_dir()?;
+        dir.push(self.year.to_string());
+        dir.push(format!("{:02}", self.day));
+        Ok(dir)
+    }
+
+    pub fn filename(&self) -> Result<PathBuf, AdventError> {
+        let mut input_filename = self.dir()?;
         input_filename.push("input");
 
-        input_filename
+        Ok(input_filename)
+    }
+
+    pub fn meta_filename(&self) -> Result<PathBuf, AdventError> {
+        let mut meta_filename = self.dir()?;
+        meta_filename.push("input.meta.json");
+
+        Ok(meta_filename)
+    }
+
+    pub fn history_dir(&self) -> Result<PathBuf, AdventError> {
+        let mut history_dir = self.dir()?;
+        history_dir.push("history");
+
+        Ok(history_dir)
+    }
+
+    pub fn puzzle_filename(&self) -> Result<PathBuf, AdventError> {
+        let mut puzzle_filename = self.dir()?;
+        puzzle_filename.push("puzzle.html");
+
+        Ok(puzzle_filename)
+    }
+
+    pub fn description_filename(&self) -> Result<PathBuf, AdventError> {
+        let mut description_filename = self.dir()?;
+        description_filename.push("puzzle.md");
+
+        Ok(description_filename)
+    }
+
+    pub fn ledger_filename(&self) -> Result<PathBuf, AdventError> {
+        let mut ledger_filename = self.dir()?;
+        ledger_filename.push("answers.jsonl");
+
+        Ok(ledger_filename)
     }
 
     pub fn exists(&self) -> bool {
-        self.filename().exists()
+        self.filename().map(|f| f.exists()).unwrap_or(false)
     }
 }
 
-pub fn get_cookie() -> String {
-    let cookie_path = cookie_file_path();
-    fs::read_to_string(&cookie_path).expect("error reading cookie file")
+pub fn has_cookie() -> bool {
+    cookie_file_path().map(|p| p.exists()).unwrap_or(false)
 }
 
-pub fn write_puzzle_input(selector: &Selector, value: String) -> Result<()> {
-    let input_filename = selector.filename();
+pub fn get_cookie() -> Result<String, AdventError> {
+    let path = cookie_file_path()?;
+    if !path.exists() {
+        return Err(AdventError::MissingCookie {
+            profile: active_profile(),
+            path,
+        });
+    }
+    fs::read_to_string(&path).map_err(AdventError::io(&path))
+}
 
-    DirBuilder::new()
-        .recursive(true)
-        .create(input_filename.parent().unwrap())?;
+pub(crate) fn write_file(filename: PathBuf, value: &str) -> Result<(), AdventError> {
+    if let Some(parent) = filename.parent() {
+        DirBuilder::new()
+            .recursive(true)
+            .create(parent)
+            .map_err(AdventError::io(parent))?;
+    }
 
-    let mut file = File::create(input_filename)?;
-    file.write_all(value.as_bytes())?;
+    let mut file = File::create(&filename).map_err(AdventError::io(&filename))?;
+    file.write_all(value.as_bytes())
+        .map_err(AdventError::io(&filename))?;
 
     Ok(())
 }
 
-pub fn read_puzzle_input(selector: &Selector) -> Result<String> {
-    let input_filename = selector.filename();
-
-    let file = File::open(input_filename)?;
+pub(crate) fn read_file(filename: PathBuf) -> Result<String, AdventError> {
+    let file = File::open(&filename).map_err(AdventError::io(&filename))?;
     let mut buf_reader = BufReader::new(file);
     let mut value = String::new();
-    buf_reader.read_to_string(&mut value)?;
+    buf_reader
+        .read_to_string(&mut value)
+        .map_err(AdventError::io(&filename))?;
     Ok(value)
 }
 
+pub fn write_puzzle_input(selector: &Selector, value: String) -> Result<()> {
+    let meta = InputMeta::new(&value, &active_profile());
+    write_file(selector.filename()?, &value)?;
+    write_file(selector.meta_filename()?, &serde_json::to_string(&meta)?)?;
+    Ok(())
+}
+
+/// writes a freshly fetched input, moving the cached one into `history/` first if it differs.
+/// returns where the previous version went.
+pub fn replace_puzzle_input(selector: &Selector, value: String) -> Result<Option<PathBuf>> {
+    let mut archived = None;
+
+    if selector.exists() {
+        let previous = read_puzzle_input(selector)?;
+        if previous != value {
+            let fetched_at = match read_puzzle_meta(selector)? {
+                Some(meta) => meta.fetched_at,
+                None => fs::metadata(selector.filename()?)?
+                    .modified()?
+                    .duration_since(std::time::UNIX_EPOCH)?
+                    .as_secs(),
+            };
+            let path = selector
+                .history_dir()?
+                .join(format!("input.{}", fetched_at));
+            write_file(path.clone(), &previous)?;
+            archived = Some(path);
+        }
+    }
+
+    write_puzzle_input(selector, value)?;
+    Ok(archived)
+}
+
+pub fn read_puzzle_input(selector: &Selector) -> Result<String> {
+    Ok(read_file(selector.filename()?)?)
+}
+
+/// inputs cached before metadata was recorded don't have any
+pub fn read_puzzle_meta(selector: &Selector) -> Result<Option<InputMeta>> {
+    let path = selector.meta_filename()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&read_file(path)?)?))
+}
+
+pub fn check_puzzle_input(selector: &Selector) -> Result<Vec<Problem>> {
+    let value = read_puzzle_input(selector)?;
+    let meta = read_puzzle_meta(selector)?;
+    Ok(integrity::check(&value, meta.as_ref()))
+}
+
+pub fn write_puzzle_page(selector: &Selector, page: &str, description: &str) -> Result<()> {
+    write_file(selector.puzzle_filename()?, page)?;
+    write_file(selector.description_filename()?, description)?;
+    Ok(())
+}
+
+pub fn read_puzzle_page(selector: &Selector) -> Result<String> {
+    Ok(read_file(selector.puzzle_filename()?)?)
+}
+
+fn input_filename_for(profile: &str, year: usize, day: usize) -> Result<PathBuf, AdventError> {
+    let mut path = input_cache_dir_for(profile)?;
+    path.push(year.to_string());
+    path.push(format!("{:02}", day));
+    path.push("input");
+    Ok(path)
+}
+
+/// the cached input, checked against what was fetched. broken ones (empty, truncated, an html
+/// page) are an error, and anything else that's off is warned about on stderr
+pub fn try_get_input_for(profile: &str, year: usize, day: usize) -> Result<String, AdventError> {
+    let path = input_filename_for(profile, year, day)?;
+    if !path.exists() {
+        return Err(AdventError::MissingInput { year, day, path });
+    }
+    let value = read_file(path.clone())?;
+
+    let meta_path = path.with_file_name("input.meta.json");
+    let meta: Option<InputMeta> = match meta_path.exists() {
+        true => serde_json::from_str(&read_file(meta_path)?).ok(),
+        false => None,
+    };
+    let problems = integrity::check(&value, meta.as_ref());
+    if let Some(problem) = problems.iter().find(|p| p.is_fatal()) {
+        return Err(AdventError::BrokenInput {
+            year,
+            day,
+            path,
+            problem: problem.to_string(),
+        });
+    }
+    for problem in problems {
+        eprintln!("warning: {}-{:02}: {}", year, day, problem);
+    }
+
+    Ok(value)
+}
+
+pub fn try_get_input(year: usize, day: usize) -> Result<String, AdventError> {
+    try_get_input_for(&active_profile(), year, day)
+}
+
 pub fn get_input(year: usize, day: usize) -> String {
-    let selector = Selector { year, day };
-    read_puzzle_input(&selector).unwrap()
+    try_get_input(year, day).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn get_input_for(profile: &str, year: usize, day: usize) -> String {
+    try_get_input_for(profile, year, day).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// (profile, input) for every profile that has this day's input cached
+pub fn try_get_inputs(year: usize, day: usize) -> Result<Vec<(String, String)>> {
+    let mut out = vec![];
+    for profile in profiles()? {
+        match try_get_input_for(&profile, year, day) {
+            Ok(input) => out.push((profile, input)),
+            Err(AdventError::MissingInput { .. }) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(out)
+}
+
+pub fn get_inputs(year: usize, day: usize) -> Vec<(String, String)> {
+    try_get_inputs(year, day).unwrap_or_else(|e| panic!("{}", e))
 }
 
 pub fn set_cookie(cookie: String, force: bool) -> Result<()> {
-    let cookie_path = cookie_file_path();
+    let cookie_path = cookie_file_path()?;
 
     if !force && cookie_path.exists() {
         return Err(Error::msg(
@@ -104,14 +457,26 @@ pub fn set_cookie(cookie: String, force: bool) -> Result<()> {
         ));
     }
 
-    DirBuilder::new().recursive(true).create(aoc_config_dir())?;
-
-    let mut file = File::create(cookie_file_path())?;
-    file.write_all(cookie.as_bytes())?;
+    write_file(cookie_path, &cookie)?;
 
     Ok(())
 }
 
+#[cfg(test)]
+static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+// points $AOC_CONFIG at a fresh temp dir for as long as the guard is held
+#[cfg(test)]
+pub(crate) fn temp_config(name: &str) -> (std::sync::MutexGuard<'static, ()>, PathBuf) {
+    let guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = env::temp_dir().join(format!("advent-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    env::set_var(AOC_CONFIG_DIR_KEY, &dir);
+    env::remove_var(AOC_PROFILE_KEY);
+    set_profile(None).unwrap();
+    (guard, dir)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -122,4 +487,107 @@ mod tests {
     fn do_thing() {
         assert_eq!(2, 2)
     }
+
+    #[test]
+    fn test_selector() {
+        assert!(Selector::new(2025, 13).is_err());
+        let days: Vec<usize> = Selector::days(2025).map(|s| s.day()).collect();
+        assert_eq!(days, (1..=12).collect::<Vec<_>>());
+        assert_eq!(Selector::days(2014).count(), 0);
+
+        let selector: Selector = (2022, 25).into();
+        assert_eq!((selector.year(), selector.day()), (2022, 25));
+    }
+
+    #[test]
+    #[should_panic(expected = "use Selector::new")]
+    fn test_selector_from_invalid() {
+        let _: Selector = (2022, 26).into();
+    }
+
+    #[test]
+    fn test_profiles() {
+        let (_guard, dir) = temp_config("profiles");
+
+        write_puzzle_input(&Selector::new(2022, 1).unwrap(), "main".to_string()).unwrap();
+        set_profile(Some("alt")).unwrap();
+        write_puzzle_input(&Selector::new(2022, 1).unwrap(), "alt".to_string()).unwrap();
+        set_profile(None).unwrap();
+
+        assert_eq!(profiles().unwrap(), vec!["default", "alt"]);
+        assert_eq!(get_input(2022, 1), "main");
+        assert_eq!(get_input_for("alt", 2022, 1), "alt");
+        assert!(dir.join("profiles/alt/input/2022/01/input").exists());
+        assert_eq!(
+            get_inputs(2022, 1),
+            vec![
+                ("default".to_string(), "main".to_string()),
+                ("alt".to_string(), "alt".to_string())
+            ]
+        );
+
+        assert!(matches!(
+            try_get_input(2022, 2),
+            Err(AdventError::MissingInput { day: 2, .. })
+        ));
+        assert!(matches!(
+            get_cookie(),
+            Err(AdventError::MissingCookie { .. })
+        ));
+
+        assert!(set_profile(Some("../escape")).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_replace_keeps_history() {
+        let (_guard, dir) = temp_config("history");
+        let selector = Selector::new(2022, 1).unwrap();
+
+        assert_eq!(
+            replace_puzzle_input(&selector, "1\n".to_string()).unwrap(),
+            None
+        );
+        assert_eq!(read_puzzle_meta(&selector).unwrap().unwrap().bytes, 2);
+        assert_eq!(
+            replace_puzzle_input(&selector, "1\n".to_string()).unwrap(),
+            None
+        );
+
+        let archived = replace_puzzle_input(&selector, "2\n".to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(read_file(archived).unwrap(), "1\n");
+        assert_eq!(read_puzzle_input(&selector).unwrap(), "2\n");
+        assert!(check_puzzle_input(&selector).unwrap().is_empty());
+
+        write_file(selector.filename().unwrap(), "2").unwrap();
+        assert_eq!(
+            check_puzzle_input(&selector).unwrap(),
+            vec![Problem::TrailingWhitespace]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_validates() {
+        let (_guard, dir) = temp_config("read-validates");
+        let selector = Selector::new(2022, 1).unwrap();
+        write_puzzle_input(&selector, "1000\n2000\n".to_string()).unwrap();
+
+        // only warned about
+        write_file(selector.filename().unwrap(), "1000\n3000\n").unwrap();
+        assert_eq!(get_input(2022, 1), "1000\n3000\n");
+
+        for broken in ["1000\n", "<!DOCTYPE html>\n<html></html>\n", ""] {
+            write_file(selector.filename().unwrap(), broken).unwrap();
+            assert!(matches!(
+                try_get_input(2022, 1),
+                Err(AdventError::BrokenInput { day: 1, .. })
+            ));
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
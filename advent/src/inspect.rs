@@ -0,0 +1,435 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+// how many of the most common characters the report lists
+const HISTOGRAM_SIZE: usize = 12;
+
+// written into a stub for inputs with negative numbers, the tests run it as it's written
+const SIGNED_NUMBERS: &str = include_str!("stubs/signed_numbers.rs");
+
+/// what an input looks like, to pick a way to parse it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub lines: usize,
+    /// shortest and longest non-blank line, in chars
+    pub widths: (usize, usize),
+    /// how many times each character shows up, newlines aside
+    pub histogram: BTreeMap<char, usize>,
+    /// how many lines are in each blank-line-separated section
+    pub sections: Vec<usize>,
+    /// how many numbers each non-blank line has
+    pub numbers_per_line: Vec<usize>,
+    /// smallest and largest number, if there are any
+    pub range: Option<(i128, i128)>,
+    pub negative: bool,
+}
+
+/// the numbers in `line`, a `-` right before one makes it negative unless it follows a
+/// letter or digit, so `x=-3` is -3 and `1-3` is 1 and 3
+fn numbers(line: &str) -> Vec<i128> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut found = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() && chars[i].is_ascii_digit() {
+            i += 1;
+        }
+        let digits: String = chars[start..i].iter().collect();
+        let Ok(number) = digits.parse::<i128>() else {
+            continue;
+        };
+        let signed = start > 0
+            && chars[start - 1] == '-'
+            && (start < 2 || !chars[start - 2].is_alphanumeric());
+        found.push(if signed { -number } else { number });
+    }
+    found
+}
+
+pub fn inspect(input: &str) -> Report {
+    let lines: Vec<&str> = input.lines().collect();
+
+    let mut sections = vec![];
+    let mut current = 0;
+    for line in lines.iter() {
+        if line.trim().is_empty() {
+            if current > 0 {
+                sections.push(current);
+            }
+            current = 0;
+        } else {
+            current += 1;
+        }
+    }
+    if current > 0 {
+        sections.push(current);
+    }
+
+    let filled: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+    let widths = filled.iter().map(|l| l.chars().count());
+    let widths = (widths.clone().min().unwrap_or(0), widths.max().unwrap_or(0));
+
+    let mut histogram = BTreeMap::new();
+    for c in input.chars().filter(|&c| c != '\n' && c != '\r') {
+        *histogram.entry(c).or_insert(0) += 1;
+    }
+
+    let found: Vec<Vec<i128>> = filled.iter().map(|l| numbers(l)).collect();
+    let all = found.iter().flatten();
+    let range = all.clone().min().copied().zip(all.clone().max().copied());
+
+    Report {
+        lines: lines.len(),
+        widths,
+        histogram,
+        sections,
+        numbers_per_line: found.iter().map(Vec::len).collect(),
+        range,
+        negative: range.is_some_and(|(min, _)| min < 0),
+    }
+}
+
+impl Report {
+    /// every line is as wide as the others, with no spaces in it
+    pub fn is_grid(&self) -> bool {
+        self.sections.len() == 1
+            && self.lines > 1
+            && self.widths.0 == self.widths.1
+            && self.widths.0 > 1
+            && !self.histogram.contains_key(&' ')
+    }
+
+    /// the number of numbers every line has, if they all have the same
+    fn uniform_numbers(&self) -> Option<usize> {
+        let first = *self.numbers_per_line.first()?;
+        self.numbers_per_line
+            .iter()
+            .all(|&n| n == first)
+            .then_some(first)
+    }
+
+    /// the existing helpers that fit this input, best first
+    pub fn suggest(&self) -> Vec<Suggestion> {
+        let mut suggestions = vec![];
+
+        if self.is_grid() {
+            let size = format!("a {}x{} grid", self.widths.0, self.lines);
+            suggestions.push(Suggestion {
+                helper: Helper::SpaceFromLines,
+                reason: format!("every line is {} wide, {}", self.widths.0, size),
+            });
+            suggestions.push(Suggestion {
+                helper: Helper::GridFromStr,
+                reason: format!("{} keyed by `advent::grid::Coordinate`", size),
+            });
+            return suggestions;
+        }
+
+        let Some((min, max)) = self.range else {
+            return suggestions;
+        };
+
+        // `columns` and `just_numbers` would both drop minus signs
+        match self.uniform_numbers() {
+            Some(n) if n > 1 && self.sections.len() == 1 && !self.negative => {
+                suggestions.push(Suggestion {
+                    helper: Helper::Columns,
+                    reason: format!("every line has {} numbers", n),
+                })
+            }
+            _ => {}
+        }
+
+        let shape = if self.sections.len() > 1 {
+            Shape::Sections
+        } else if self.uniform_numbers() == Some(1) {
+            Shape::Flat
+        } else {
+            Shape::Lines
+        };
+        let reason = match shape {
+            Shape::Sections => format!("{} sections of numbers", self.sections.len()),
+            Shape::Flat => "one number a line".to_string(),
+            Shape::Lines => "numbers on each line".to_string(),
+        };
+        suggestions.push(match self.negative {
+            true => Suggestion {
+                helper: Helper::SignedNumbers(shape),
+                reason: format!("{}, from {} to {}", reason, min, max),
+            },
+            false => Suggestion {
+                helper: Helper::JustNumbers(shape),
+                reason: format!("{}, from {} to {}", reason, min, max),
+            },
+        });
+
+        suggestions
+    }
+
+    /// a `type Input` and `fn parse` for the day's `Solution`, using `helper`
+    pub fn stub(&self, helper: Helper) -> String {
+        let list = |shape: Shape, n: &str| match shape {
+            Shape::Flat => format!("Vec<{}>", n),
+            Shape::Lines | Shape::Sections => format!("Vec<Vec<{}>>", n),
+        };
+        let parse = |shape: Shape, f: &str| match shape {
+            Shape::Flat => format!("{}(input)", f),
+            Shape::Lines => format!("input.lines().map({}).collect()", f),
+            Shape::Sections => format!("input.split(\"\\n\\n\").map({}).collect()", f),
+        };
+
+        let (import, input, body) = match helper {
+            Helper::SpaceFromLines => (
+                "use advent_toolbox::spatial::{Coordinate, Space};".to_string(),
+                "Space<Coordinate, char>".to_string(),
+                "Space::from_lines(input.trim())".to_string(),
+            ),
+            Helper::GridFromStr => (
+                "use advent::grid::{self, Grid};".to_string(),
+                "Grid<char>".to_string(),
+                "grid::from_str(input).unwrap()".to_string(),
+            ),
+            Helper::Columns => (
+                "use advent_toolbox::parser_helpers::columns;".to_string(),
+                "Vec<Vec<usize>>".to_string(),
+                "columns(input.trim())".to_string(),
+            ),
+            Helper::JustNumbers(shape) => (
+                "use advent_toolbox::parser_helpers::just_numbers;".to_string(),
+                list(shape, "usize"),
+                parse(shape, "just_numbers"),
+            ),
+            Helper::SignedNumbers(shape) => (
+                SIGNED_NUMBERS.trim_end().to_string(),
+                list(shape, "isize"),
+                parse(shape, "signed_numbers"),
+            ),
+        };
+
+        format!(
+            "{}\n\n    type Input = {};\n\n    fn parse(input: &str) -> Self::Input {{\n        {}\n    }}\n",
+            import, input, body
+        )
+    }
+}
+
+/// how numbers are grouped, for `just_numbers`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// all of them in one list
+    Flat,
+    /// a list per line
+    Lines,
+    /// a list per blank-line-separated section
+    Sections,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Helper {
+    /// `advent_toolbox::spatial::Space::from_lines`
+    SpaceFromLines,
+    /// `advent::grid::from_str`
+    GridFromStr,
+    /// `advent_toolbox::parser_helpers::columns`
+    Columns,
+    /// `advent_toolbox::parser_helpers::just_numbers`
+    JustNumbers(Shape),
+    /// a `signed_numbers` written into the stub, for numbers that can be negative
+    SignedNumbers(Shape),
+}
+
+impl fmt::Display for Helper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Helper::SpaceFromLines => "Space::from_lines",
+            Helper::GridFromStr => "advent::grid::from_str",
+            Helper::Columns => "parser_helpers::columns",
+            Helper::JustNumbers(_) => "parser_helpers::just_numbers",
+            Helper::SignedNumbers(_) => "a signed_numbers fn in the stub",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    pub helper: Helper,
+    pub reason: String,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} lines", self.lines)?;
+        if self.sections.len() > 1 {
+            let sizes: Vec<String> = self.sections.iter().map(|s| s.to_string()).collect();
+            write!(
+                f,
+                " in {} sections ({} lines)",
+                self.sections.len(),
+                sizes.join(", ")
+            )?;
+        }
+        writeln!(f)?;
+
+        let (narrowest, widest) = self.widths;
+        if narrowest == widest {
+            writeln!(f, "every line is {} wide", widest)?;
+        } else {
+            writeln!(f, "lines are {} to {} wide", narrowest, widest)?;
+        }
+
+        let mut common: Vec<(&char, &usize)> = self.histogram.iter().collect();
+        common.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let shown: Vec<String> = common
+            .iter()
+            .take(HISTOGRAM_SIZE)
+            .map(|(c, n)| format!("{:?} {}", c, n))
+            .collect();
+        write!(f, "{} distinct chars: {}", common.len(), shown.join(", "))?;
+        if common.len() > HISTOGRAM_SIZE {
+            write!(f, ", ...")?;
+        }
+        writeln!(f)?;
+
+        match self.range {
+            Some((min, max)) => writeln!(
+                f,
+                "{} numbers from {} to {}, {}",
+                self.numbers_per_line.iter().sum::<usize>(),
+                min,
+                max,
+                match self.negative {
+                    true => "some negative",
+                    false => "none negative",
+                }
+            ),
+            None => writeln!(f, "no numbers"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    const GRID: &str = include_str!("../fixtures/inspect/grid.txt");
+    const COLUMNS: &str = include_str!("../fixtures/inspect/columns.txt");
+    const SECTIONS: &str = include_str!("../fixtures/inspect/sections.txt");
+    const SIGNED: &str = include_str!("../fixtures/inspect/signed.txt");
+
+    #[rstest]
+    #[case("x=-3, y=12", vec![-3, 12])]
+    #[case("1-3 a", vec![1, 3])]
+    #[case("a-b c-10", vec![10])]
+    #[case("no numbers", vec![])]
+    fn test_numbers(#[case] line: &str, #[case] expected: Vec<i128>) {
+        assert_eq!(numbers(line), expected);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let report = inspect(SECTIONS);
+        assert_eq!(report.lines, 8);
+        assert_eq!(report.sections, vec![3, 1, 2]);
+        assert_eq!(report.widths, (4, 4));
+        assert_eq!(report.histogram[&'0'], 18);
+        assert_eq!(report.range, Some((1000, 6000)));
+        assert!(!report.negative);
+        assert!(!report.is_grid());
+
+        let report = inspect(SIGNED);
+        assert_eq!(report.numbers_per_line, vec![4, 4, 4]);
+        assert_eq!(report.range, Some((-3, 10)));
+        assert!(report.negative);
+
+        assert!(inspect(GRID).is_grid());
+        assert!(!inspect(COLUMNS).is_grid());
+    }
+
+    #[rstest]
+    #[case(GRID, vec![Helper::SpaceFromLines, Helper::GridFromStr])]
+    #[case(COLUMNS, vec![Helper::Columns, Helper::JustNumbers(Shape::Lines)])]
+    #[case(SECTIONS, vec![Helper::JustNumbers(Shape::Sections)])]
+    #[case("1\n2\n3\n", vec![Helper::JustNumbers(Shape::Flat)])]
+    #[case("abc\nde\n", vec![])]
+    fn test_suggest(#[case] input: &str, #[case] expected: Vec<Helper>) {
+        let helpers: Vec<Helper> = inspect(input)
+            .suggest()
+            .into_iter()
+            .map(|s| s.helper)
+            .collect();
+        assert_eq!(helpers, expected);
+    }
+
+    include!("stubs/signed_numbers.rs");
+
+    #[rstest]
+    #[case("x=-3, y=12", vec![-3, 12])]
+    #[case("1-3 a", vec![1, 3])]
+    #[case("a-b c-10", vec![10])]
+    #[case("-7\n--2", vec![-7])]
+    fn test_signed_numbers(#[case] line: &str, #[case] expected: Vec<isize>) {
+        assert_eq!(signed_numbers(line), expected);
+    }
+
+    #[test]
+    fn test_signed_stub() {
+        let report = inspect(SIGNED);
+        let helpers: Vec<Helper> = report.suggest().into_iter().map(|s| s.helper).collect();
+        assert_eq!(helpers, vec![Helper::SignedNumbers(Shape::Lines)]);
+
+        let stub = report.stub(Helper::SignedNumbers(Shape::Lines));
+        assert!(stub.starts_with(SIGNED_NUMBERS));
+        assert!(stub.contains("type Input = Vec<Vec<isize>>;"));
+        assert!(stub.contains("input.lines().map(signed_numbers).collect()"));
+
+        // what that stub's parse gives
+        let parsed: Vec<Vec<isize>> = SIGNED.lines().map(signed_numbers).collect();
+        assert_eq!(
+            parsed,
+            vec![vec![0, 4, 3, -3], vec![6, 3, -1, -3], vec![10, 3, -1, 2]]
+        );
+    }
+
+    #[test]
+    fn test_stub() {
+        let report = inspect(COLUMNS);
+        assert_eq!(
+            report.stub(Helper::Columns),
+            [
+                "use advent_toolbox::parser_helpers::columns;",
+                "",
+                "    type Input = Vec<Vec<usize>>;",
+                "",
+                "    fn parse(input: &str) -> Self::Input {",
+                "        columns(input.trim())",
+                "    }",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let expected = [
+            "3 lines",
+            "every line is 5 wide",
+            "2 distinct chars: '.' 9, '#' 6",
+            "no numbers",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(inspect(GRID).to_string(), expected);
+    }
+}
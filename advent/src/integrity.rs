@@ -0,0 +1,165 @@
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+
+use crate::session;
+
+/// written next to every cached input, so we can tell if it changed after it was fetched
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputMeta {
+    pub fetched_at: u64,
+    pub profile: String,
+    pub bytes: usize,
+    pub sha256: String,
+    // lets a mismatch be blamed on an editor touching the trailing newline
+    pub trimmed_sha256: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    Empty,
+    Html,
+    Truncated { expected: usize, actual: usize },
+    TrailingWhitespace,
+    Modified,
+}
+
+impl Problem {
+    /// problems that mean the cached input is useless and should be fetched again
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Problem::Empty | Problem::Html | Problem::Truncated { .. }
+        )
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "input is empty"),
+            Problem::Html => write!(f, "input is an html page, not a puzzle input"),
+            Problem::Truncated { expected, actual } => write!(
+                f,
+                "input is truncated: {} bytes, fetched {}",
+                actual, expected
+            ),
+            Problem::TrailingWhitespace => {
+                write!(
+                    f,
+                    "input's trailing whitespace changed since it was fetched"
+                )
+            }
+            Problem::Modified => write!(f, "input changed since it was fetched"),
+        }
+    }
+}
+
+pub fn sha256(value: &str) -> String {
+    Sha256::digest(value.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+impl InputMeta {
+    pub fn new(value: &str, profile: &str) -> Self {
+        Self {
+            fetched_at: now(),
+            profile: profile.to_string(),
+            bytes: value.len(),
+            sha256: sha256(value),
+            trimmed_sha256: sha256(value.trim_end()),
+        }
+    }
+}
+
+fn looks_like_html(value: &str) -> bool {
+    let start = value.trim_start().to_ascii_lowercase();
+    start.starts_with("<!doctype") || start.starts_with("<html") || session::is_logged_out(value)
+}
+
+pub fn check(value: &str, meta: Option<&InputMeta>) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    if value.trim().is_empty() {
+        problems.push(Problem::Empty);
+        return problems;
+    }
+    if looks_like_html(value) {
+        problems.push(Problem::Html);
+        return problems;
+    }
+
+    if let Some(meta) = meta {
+        if sha256(value) != meta.sha256 {
+            if sha256(value.trim_end()) == meta.trimmed_sha256 {
+                problems.push(Problem::TrailingWhitespace);
+            } else if value.len() < meta.bytes {
+                problems.push(Problem::Truncated {
+                    expected: meta.bytes,
+                    actual: value.len(),
+                });
+            } else {
+                problems.push(Problem::Modified);
+            }
+        }
+    }
+
+    problems
+}
+
+pub fn diff(previous: &str, current: &str) -> String {
+    TextDiff::from_lines(previous, current)
+        .unified_diff()
+        .context_radius(2)
+        .header("previous", "fetched")
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    const INPUT: &str = "1abc2\npqr3stu8vwx\n";
+
+    #[rstest]
+    #[case(INPUT, vec![])]
+    #[case("", vec![Problem::Empty])]
+    #[case("\n", vec![Problem::Empty])]
+    #[case("<!DOCTYPE html>\n<html></html>", vec![Problem::Html])]
+    #[case("1abc2\npqr3stu8vwx", vec![Problem::TrailingWhitespace])]
+    #[case("1abc2\npqr3stu8vwx\n\n", vec![Problem::TrailingWhitespace])]
+    #[case("1abc2\npqr", vec![Problem::Truncated { expected: 18, actual: 9 }])]
+    #[case("1abc2\npqr3stu8vwy\n", vec![Problem::Modified])]
+    fn test_check(#[case] value: &str, #[case] expected: Vec<Problem>) {
+        let meta = InputMeta::new(INPUT, "default");
+        assert_eq!(check(value, Some(&meta)), expected);
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let diff = diff("1\n2\n3\n", "1\n5\n3\n");
+        assert!(diff.contains("-2\n"));
+        assert!(diff.contains("+5\n"));
+    }
+}
@@ -0,0 +1,259 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::answer::{Hint, Verdict};
+use crate::calendar;
+use crate::input_store::{self, Selector};
+use crate::integrity::InputMeta;
+use crate::repo::Solution;
+use crate::status::YearStatus;
+
+// the shapes `advent --json` prints, so other tools don't need to know the input_store layout
+
+/// where the active profile keeps everything
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProfilePaths {
+    pub profile: String,
+    pub config: PathBuf,
+    pub dir: PathBuf,
+    pub inputs: PathBuf,
+    pub cookie: PathBuf,
+    pub has_cookie: bool,
+}
+
+impl ProfilePaths {
+    pub fn active() -> Result<Self> {
+        Ok(Self {
+            profile: input_store::active_profile(),
+            config: input_store::aoc_config_dir()?,
+            dir: input_store::active_profile_dir()?,
+            inputs: input_store::input_cache_dir()?,
+            cookie: input_store::cookie_file_path()?,
+            has_cookie: input_store::has_cookie(),
+        })
+    }
+}
+
+/// where a day's files are cached, whether or not they exist yet
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DayPaths {
+    pub dir: PathBuf,
+    pub input: PathBuf,
+    pub meta: PathBuf,
+    pub puzzle: PathBuf,
+    pub description: PathBuf,
+    pub answers: PathBuf,
+}
+
+impl DayPaths {
+    pub fn new(selector: &Selector) -> Result<Self> {
+        Ok(Self {
+            dir: selector.dir()?,
+            input: selector.filename()?,
+            meta: selector.meta_filename()?,
+            puzzle: selector.puzzle_filename()?,
+            description: selector.description_filename()?,
+            answers: selector.ledger_filename()?,
+        })
+    }
+}
+
+/// a day's cached input and what we know about it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DayInput {
+    pub year: usize,
+    pub day: usize,
+    pub cached: bool,
+    pub paths: DayPaths,
+    pub meta: Option<InputMeta>,
+    pub problems: Vec<String>,
+    /// where a refetch kept the input it replaced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+}
+
+impl DayInput {
+    pub fn load(selector: &Selector) -> Result<Self> {
+        let cached = selector.exists();
+        let problems = match cached {
+            true => input_store::check_puzzle_input(selector)?
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            false => vec![],
+        };
+
+        Ok(Self {
+            year: selector.year(),
+            day: selector.day(),
+            cached,
+            paths: DayPaths::new(selector)?,
+            meta: input_store::read_puzzle_meta(selector)?,
+            problems,
+            previous: None,
+            input: None,
+        })
+    }
+}
+
+/// a submission and the site's verdict on it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Submission {
+    pub year: usize,
+    pub day: usize,
+    pub part: u16,
+    pub answer: String,
+    /// `correct`, `wrong`, `already_solved`, `rate_limited` or `unknown`
+    pub verdict: &'static str,
+    /// `too_high` or `too_low`, when the site said
+    pub hint: Option<&'static str>,
+    /// seconds to wait before submitting again
+    pub wait: Option<u64>,
+    pub message: String,
+}
+
+impl Submission {
+    pub fn new(selector: &Selector, part: u16, answer: &str, verdict: &Verdict) -> Self {
+        let (name, hint, wait) = match verdict {
+            Verdict::Correct => ("correct", None, None),
+            Verdict::Wrong { hint, wait } => ("wrong", *hint, *wait),
+            Verdict::AlreadySolved => ("already_solved", None, None),
+            Verdict::RateLimited { wait } => ("rate_limited", None, Some(*wait)),
+            Verdict::Unknown(_) => ("unknown", None, None),
+        };
+
+        Self {
+            year: selector.year(),
+            day: selector.day(),
+            part,
+            answer: answer.to_string(),
+            verdict: name,
+            hint: hint.map(|h| match h {
+                Hint::TooHigh => "too_high",
+                Hint::TooLow => "too_low",
+            }),
+            wait: wait.map(|w| w.as_secs()),
+            message: verdict.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DayStatus {
+    pub day: usize,
+    /// `None` when the site couldn't be asked
+    pub stars: Option<u8>,
+    /// `done`, `partial`, `not_done` or `missing`
+    pub solution: &'static str,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Year {
+    pub year: usize,
+    pub days: Vec<DayStatus>,
+    /// days with stars on the site but no finished solution here
+    pub gaps: Vec<usize>,
+}
+
+impl From<&YearStatus> for Year {
+    fn from(status: &YearStatus) -> Self {
+        let days = calendar::days(status.year)
+            .map(|day| DayStatus {
+                day,
+                stars: status.stars_on(day),
+                solution: match status.solution(day) {
+                    Solution::Done => "done",
+                    Solution::Partial => "partial",
+                    Solution::NotDone => "not_done",
+                    Solution::Missing => "missing",
+                },
+            })
+            .collect();
+
+        Self {
+            year: status.year,
+            days,
+            gaps: status.gaps(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn test_day_input() {
+        let (_guard, dir) = input_store::temp_config("json");
+        let selector = Selector::new(2022, 1).unwrap();
+
+        let missing = DayInput::load(&selector).unwrap();
+        assert!(!missing.cached);
+        assert!(missing.meta.is_none());
+        assert_eq!(
+            missing.paths.input,
+            dir.join("input").join("2022").join("01").join("input")
+        );
+
+        input_store::write_puzzle_input(&selector, "1\n2\n".to_string()).unwrap();
+        let value = serde_json::to_value(DayInput::load(&selector).unwrap()).unwrap();
+        assert_eq!(value["cached"], json!(true));
+        assert_eq!(value["meta"]["bytes"], json!(4));
+        assert_eq!(value["problems"], json!([]));
+        assert!(value.get("input").is_none());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_submission() {
+        let selector = Selector::new(2022, 1).unwrap();
+        let verdict = Verdict::Wrong {
+            hint: Some(Hint::TooLow),
+            wait: Some(Duration::from_secs(60)),
+        };
+        let value = serde_json::to_value(Submission::new(&selector, 2, "12", &verdict)).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "year": 2022,
+                "day": 1,
+                "part": 2,
+                "answer": "12",
+                "verdict": "wrong",
+                "hint": "too_low",
+                "wait": 60,
+                "message": verdict.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_year() {
+        let status = YearStatus {
+            year: 2025,
+            stars: Some(vec![(1, 2), (2, 1)].into_iter().collect()),
+            solutions: vec![(1, Solution::Done), (2, Solution::Partial)]
+                .into_iter()
+                .collect(),
+        };
+        let year = Year::from(&status);
+        assert_eq!(year.days.len(), 12);
+        assert_eq!(
+            year.days[1],
+            DayStatus {
+                day: 2,
+                stars: Some(1),
+                solution: "partial"
+            }
+        );
+        assert_eq!(year.days[2].solution, "missing");
+        assert_eq!(year.gaps, vec![2]);
+    }
+}
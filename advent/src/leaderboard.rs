@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::answer::format_wait;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+    pub star_index: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: u64,
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub day1_ts: Option<u64>,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sort {
+    LocalScore,
+    Stars,
+    Name,
+    LastStar,
+}
+
+const DAY: u64 = 24 * 60 * 60;
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star(&self, day: usize, part: u16) -> Option<&Star> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+    }
+
+    pub fn stars_on(&self, day: usize) -> usize {
+        (1..=2).filter(|&p| self.star(day, p).is_some()).count()
+    }
+
+    /// how long part 2 took after part 1
+    pub fn delta(&self, day: usize) -> Option<Duration> {
+        let p1 = self.star(day, 1)?.get_star_ts;
+        let p2 = self.star(day, 2)?.get_star_ts;
+        Some(Duration::from_secs(p2.saturating_sub(p1)))
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(payload: &str) -> Result<Self> {
+        Ok(serde_json::from_str(payload)?)
+    }
+
+    pub fn sorted(&self, sort: Sort) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        // ties go to whoever got there first, like the site does
+        members.sort_by_key(|m| (m.last_star_ts, m.id));
+        match sort {
+            Sort::LocalScore => members.sort_by_key(|m| std::cmp::Reverse(m.local_score)),
+            Sort::Stars => members.sort_by_key(|m| std::cmp::Reverse(m.stars)),
+            Sort::Name => members.sort_by_key(|m| m.display_name().to_lowercase()),
+            Sort::LastStar => members.sort_by_key(|m| std::cmp::Reverse(m.last_star_ts)),
+        }
+        members
+    }
+
+    fn unlock_ts(&self, day: usize) -> Option<u64> {
+        self.day1_ts.map(|ts| ts + (day as u64 - 1) * DAY)
+    }
+
+    pub fn render(&self, sort: Sort, days: usize) -> String {
+        let mut out = String::new();
+        let prefix = " ".repeat(17);
+
+        let tens: String = (1..=days).map(tens_digit).collect();
+        let ones: String = (1..=days)
+            .map(|d| char::from_digit((d % 10) as u32, 10).unwrap())
+            .collect();
+        writeln!(out, "{prefix}{}", tens.trim_end()).unwrap();
+        writeln!(out, "     score stars  {}", ones).unwrap();
+
+        for (rank, member) in self.sorted(sort).iter().enumerate() {
+            let grid: String = (1..=days)
+                .map(|d| match member.stars_on(d) {
+                    2 => '★',
+                    1 => '☆',
+                    _ => '·',
+                })
+                .collect();
+            writeln!(
+                out,
+                "{:>3}) {:>5} {:>5}  {} {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                grid,
+                member.display_name()
+            )
+            .unwrap();
+        }
+
+        out
+    }
+
+    /// when each member finished each part of `day`, and how long part 2 took
+    pub fn render_day(&self, sort: Sort, day: usize) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "day {:<2}  {:>9} {:>9} {:>9}",
+            day, "part 1", "part 2", "delta"
+        )
+        .unwrap();
+
+        let unlock = self.unlock_ts(day);
+        let since_unlock = |star: Option<&Star>| match (star, unlock) {
+            (Some(star), Some(unlock)) => {
+                format_wait(Duration::from_secs(star.get_star_ts.saturating_sub(unlock)))
+            }
+            (Some(_), None) => "done".to_string(),
+            (None, _) => "-".to_string(),
+        };
+
+        for member in self.sorted(sort) {
+            if member.stars_on(day) == 0 {
+                continue;
+            }
+            let delta = member.delta(day).map(format_wait);
+            writeln!(
+                out,
+                "        {:>9} {:>9} {:>9}  {}",
+                since_unlock(member.star(day, 1)),
+                since_unlock(member.star(day, 2)),
+                delta.as_deref().unwrap_or("-"),
+                member.display_name()
+            )
+            .unwrap();
+        }
+
+        out
+    }
+}
+
+fn tens_digit(day: usize) -> char {
+    match day / 10 {
+        0 => ' ',
+        n => char::from_digit(n as u32, 10).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    const SAMPLE: &str = include_str!("../fixtures/leaderboard/sample.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = Leaderboard::parse(SAMPLE).unwrap();
+        assert_eq!(leaderboard.event, "2022");
+        assert_eq!(leaderboard.members.len(), 3);
+
+        let alice = &leaderboard.members["1001"];
+        assert_eq!(alice.stars_on(2), 2);
+        assert_eq!(alice.stars_on(3), 1);
+        assert_eq!(alice.delta(2), Some(Duration::from_secs(3600)));
+        assert_eq!(alice.delta(3), None);
+    }
+
+    #[rstest]
+    #[case(Sort::LocalScore, vec!["alice", "bob", "(anonymous user #1003)"])]
+    #[case(Sort::Stars, vec!["alice", "bob", "(anonymous user #1003)"])]
+    #[case(Sort::Name, vec!["(anonymous user #1003)", "alice", "bob"])]
+    #[case(Sort::LastStar, vec!["alice", "bob", "(anonymous user #1003)"])]
+    fn test_sorted(#[case] sort: Sort, #[case] expected: Vec<&str>) {
+        let leaderboard = Leaderboard::parse(SAMPLE).unwrap();
+        let names: Vec<String> = leaderboard
+            .sorted(sort)
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = Leaderboard::parse(SAMPLE).unwrap();
+        let expected = [
+            "                          1",
+            "     score stars  1234567890",
+            "  1)    13     5  ★★☆······· alice",
+            "  2)    10     4  ★★········ bob",
+            "  3)     0     0  ·········· (anonymous user #1003)",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(leaderboard.render(Sort::LocalScore, 10), expected);
+    }
+
+    #[test]
+    fn test_render_day() {
+        let leaderboard = Leaderboard::parse(SAMPLE).unwrap();
+        let expected = [
+            "day 2      part 1    part 2     delta",
+            "              10m    1h 10m     1h 0m  alice",
+            "              15m       20m        5m  bob",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(leaderboard.render_day(Sort::LocalScore, 2), expected);
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::{DirBuilder, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::answer::{Hint, Verdict};
+use crate::error::AdventError;
+use crate::input_store::Selector;
+
+// answers that are never worth a submission, eg. the template's output
+const PLACEHOLDERS: &[&str] = &["", "not done", "todo"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub part: u16,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub timestamp: u64,
+}
+
+impl Entry {
+    pub fn new(part: u16, answer: &str, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            part,
+            answer: answer.trim().to_string(),
+            verdict,
+            timestamp,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    Placeholder(String),
+    AlreadySolved(String),
+    AlreadyRejected(Verdict),
+    TooHigh { bound: i128 },
+    TooLow { bound: i128 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Placeholder(answer) => write!(f, "{:?} looks like a placeholder", answer),
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with {}", answer)
+            }
+            Refusal::AlreadyRejected(verdict) => {
+                write!(f, "this answer was already submitted: {}", verdict)
+            }
+            Refusal::TooHigh { bound } => {
+                write!(f, "{} was already too high, answer must be lower", bound)
+            }
+            Refusal::TooLow { bound } => {
+                write!(f, "{} was already too low, answer must be higher", bound)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub entries: Vec<Entry>,
+}
+
+impl Ledger {
+    pub fn load(selector: &Selector) -> Result<Self> {
+        let path = selector.ledger_filename()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(&path).map_err(AdventError::io(&path))?;
+        let mut entries = vec![];
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn record(selector: &Selector, entry: &Entry) -> Result<()> {
+        let path = selector.ledger_filename()?;
+        let dir = selector.dir()?;
+        DirBuilder::new()
+            .recursive(true)
+            .create(&dir)
+            .map_err(AdventError::io(&dir))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(AdventError::io(&path))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+
+        Ok(())
+    }
+
+    /// records answers known to be right, eg. from a solved puzzle's page, returning the parts that were new
+    pub fn learn(selector: &Selector, answers: &BTreeMap<u16, String>) -> Result<Vec<u16>> {
+        let ledger = Self::load(selector)?;
+        let mut learned = vec![];
+
+        for (&part, answer) in answers {
+            if ledger.solution(part).is_none() {
+                Self::record(selector, &Entry::new(part, answer, Verdict::Correct))?;
+                learned.push(part);
+            }
+        }
+
+        Ok(learned)
+    }
+
+    /// the right answer for each part that has one
+    pub fn solutions(&self) -> BTreeMap<u16, String> {
+        self.entries
+            .iter()
+            .filter(|e| e.verdict.is_correct())
+            .map(|e| (e.part, e.answer.clone()))
+            .collect()
+    }
+
+    pub fn for_part(&self, part: u16) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(move |e| e.part == part)
+    }
+
+    pub fn solution(&self, part: u16) -> Option<&str> {
+        self.for_part(part)
+            .find(|e| e.verdict.is_correct())
+            .map(|e| e.answer.as_str())
+    }
+
+    /// the (lower, upper) exclusive range a numeric answer must fall in, from previous hints
+    pub fn bounds(&self, part: u16) -> (Option<i128>, Option<i128>) {
+        let mut lower = None;
+        let mut upper = None;
+
+        for entry in self.for_part(part) {
+            let Ok(value) = entry.answer.parse::<i128>() else {
+                continue;
+            };
+            match entry.verdict {
+                Verdict::Wrong {
+                    hint: Some(Hint::TooLow),
+                    ..
+                } => lower = lower.max(Some(value)),
+                Verdict::Wrong {
+                    hint: Some(Hint::TooHigh),
+                    ..
+                } => upper = Some(upper.map_or(value, |u: i128| u.min(value))),
+                _ => {}
+            }
+        }
+
+        (lower, upper)
+    }
+
+    pub fn check(&self, part: u16, answer: &str) -> Result<(), Refusal> {
+        let answer = answer.trim();
+
+        if PLACEHOLDERS.contains(&answer.to_lowercase().as_str()) {
+            return Err(Refusal::Placeholder(answer.to_string()));
+        }
+
+        if let Some(solution) = self.solution(part) {
+            return Err(Refusal::AlreadySolved(solution.to_string()));
+        }
+
+        let rejected = self
+            .for_part(part)
+            .find(|e| e.answer == answer && matches!(e.verdict, Verdict::Wrong { .. }));
+        if let Some(entry) = rejected {
+            return Err(Refusal::AlreadyRejected(entry.verdict.clone()));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let (lower, upper) = self.bounds(part);
+            if let Some(bound) = upper.filter(|&u| value >= u) {
+                return Err(Refusal::TooHigh { bound });
+            }
+            if let Some(bound) = lower.filter(|&l| value <= l) {
+                return Err(Refusal::TooLow { bound });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Refusal {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    fn wrong(part: u16, answer: &str, hint: Option<Hint>) -> Entry {
+        Entry::new(part, answer, Verdict::Wrong { hint, wait: None })
+    }
+
+    fn ledger() -> Ledger {
+        Ledger {
+            entries: vec![
+                wrong(1, "100", Some(Hint::TooHigh)),
+                wrong(1, "150", Some(Hint::TooHigh)),
+                wrong(1, "20", Some(Hint::TooLow)),
+                wrong(1, "50", None),
+                Entry::new(2, "abc", Verdict::Correct),
+            ],
+        }
+    }
+
+    #[rstest]
+    #[case(1, "60", Ok(()))]
+    #[case(1, " 99 ", Ok(()))]
+    #[case(1, "not done", Err(Refusal::Placeholder("not done".to_string())))]
+    #[case(1, "", Err(Refusal::Placeholder("".to_string())))]
+    #[case(1, "50", Err(Refusal::AlreadyRejected(Verdict::Wrong { hint: None, wait: None })))]
+    #[case(1, "100", Err(Refusal::AlreadyRejected(Verdict::Wrong { hint: Some(Hint::TooHigh), wait: None })))]
+    #[case(1, "120", Err(Refusal::TooHigh { bound: 100 }))]
+    #[case(1, "-5", Err(Refusal::TooLow { bound: 20 }))]
+    #[case(2, "def", Err(Refusal::AlreadySolved("abc".to_string())))]
+    fn test_check(#[case] part: u16, #[case] answer: &str, #[case] expected: Result<(), Refusal>) {
+        assert_eq!(ledger().check(part, answer), expected);
+    }
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let entry = wrong(1, "42", Some(Hint::TooLow));
+        let line = serde_json::to_string(&entry).unwrap();
+        let parsed: Entry = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed, entry);
+    }
+
+    #[test]
+    fn test_learn() {
+        let (_guard, dir) = crate::input_store::temp_config("ledger");
+        let selector = Selector::new(2022, 1).unwrap();
+        Ledger::record(&selector, &Entry::new(1, "7", Verdict::Correct)).unwrap();
+
+        let answers = vec![(1, "7".to_string()), (2, "5".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(Ledger::learn(&selector, &answers).unwrap(), vec![2]);
+        assert!(Ledger::learn(&selector, &answers).unwrap().is_empty());
+        assert_eq!(Ledger::load(&selector).unwrap().solutions(), answers);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -7,11 +7,39 @@ pub mod fetch;
 #[cfg(feature = "parse")]
 pub mod parse;
 pub mod parsers;
+pub mod puzzle;
 
+pub mod answer;
+pub mod article;
+pub mod bench;
+pub mod calendar;
+pub mod config;
+pub mod cooldown;
 pub mod dimension;
+pub mod error;
+pub mod examples;
 pub mod grid;
+pub mod html;
 pub mod input_store;
+pub mod inspect;
+pub mod integrity;
+pub mod json;
+pub mod leaderboard;
+pub mod ledger;
 pub mod machine;
 pub mod numbers;
+pub mod ocr;
+pub mod repo;
 pub mod ring;
+pub mod run;
+pub mod runner;
+pub mod scaffold;
+pub mod schedule;
+pub mod session;
+pub mod source;
 pub mod space;
+pub mod status;
+#[cfg(feature = "fetch")]
+pub mod sync;
+pub mod verify;
+pub mod watch;
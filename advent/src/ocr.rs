@@ -0,0 +1,94 @@
+// the letters adventofcode.com draws answers with, four columns wide and six rows tall
+const GLYPHS: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+fn lit(c: char) -> bool {
+    c != '.' && !c.is_whitespace()
+}
+
+/// reads letters drawn with `#` (or `█`, or anything else) on `.` or spaces, `None` if any of
+/// them isn't one the site draws
+pub fn read(drawing: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = drawing
+        .lines()
+        .map(|line| line.chars().map(lit).collect())
+        .collect();
+    let first = rows.iter().position(|row| row.contains(&true))?;
+    let last = rows.iter().rposition(|row| row.contains(&true))?;
+    let rows = &rows[first..=last];
+    if rows.len() != 6 {
+        return None;
+    }
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let at = |row: &Vec<bool>, col: usize| row.get(col).copied().unwrap_or(false);
+    let column_lit = |col: usize| rows.iter().any(|row| at(row, col));
+
+    let mut letters = String::new();
+    let mut col = 0;
+    while col < width {
+        if !column_lit(col) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < width && column_lit(col) {
+            col += 1;
+        }
+
+        let glyph: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                (start..col)
+                    .map(|c| if at(row, c) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let (letter, _) = GLYPHS.iter().find(|(_, rows)| rows[..] == glyph[..])?;
+        letters.push(*letter);
+    }
+
+    Some(letters)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let drawing = include_str!("../fixtures/ocr/letters.txt");
+        assert_eq!(read(drawing).as_deref(), Some("EHZRUPCJ"));
+
+        let blocks = drawing.replace('#', "█").replace('.', " ");
+        assert_eq!(
+            read(&format!("\n{}\n", blocks)).as_deref(),
+            Some("EHZRUPCJ")
+        );
+    }
+
+    #[test]
+    fn test_unreadable() {
+        assert_eq!(read("#..#\n####\n#..#\n"), None);
+        assert_eq!(read(""), None);
+
+        let smudged = include_str!("../fixtures/ocr/letters.txt").replacen('.', "#", 1);
+        assert_eq!(read(&smudged), None);
+    }
+}
@@ -13,11 +13,11 @@ use nom::{
 /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and
 /// trailing whitespace, returning the output of `inner`.
 // from https://github.com/Geal/nom/blob/master/doc/nom_recipes.md
-pub fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(
+pub fn ws<'a, F, O, E: ParseError<&'a str>>(
     inner: F,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
 where
-    F: Fn(&'a str) -> IResult<&'a str, O, E>,
+    F: Fn(&'a str) -> IResult<&'a str, O, E> + 'a,
 {
     delimited(multispace0, inner, multispace0)
 }
@@ -29,7 +29,7 @@ pub fn parse_isize(input: &str) -> IResult<&str, isize> {
     let num = {
         let n: isize = num.parse().unwrap();
         match sign {
-            Some('-') => n * -1,
+            Some('-') => -n,
             _ => n,
         }
     };
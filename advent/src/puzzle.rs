@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Error, Result};
+
+use crate::{calendar, examples, ocr};
+
+/// what a part comes up with. `Display` gives what goes after `part_1 => `.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    /// for the answers too big for `Int`
+    Big(u128),
+    Text(String),
+    /// letters drawn across several lines, read with `ocr::read`
+    Ocr(String),
+    #[default]
+    NotDone,
+}
+
+impl Answer {
+    pub fn ocr(drawing: impl Into<String>) -> Self {
+        Answer::Ocr(drawing.into())
+    }
+
+    pub fn is_done(&self) -> bool {
+        *self != Answer::NotDone
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Big(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            // kept to one line so it can be parsed back, even when the letters can't be read
+            Answer::Ocr(drawing) => match ocr::read(drawing) {
+                Some(letters) => write!(f, "{}", letters),
+                None => {
+                    let rows: Vec<&str> =
+                        drawing.lines().filter(|l| !l.trim().is_empty()).collect();
+                    write!(f, "{}", rows.join("/"))
+                }
+            },
+            Answer::NotDone => write!(f, "not done"),
+        }
+    }
+}
+
+macro_rules! int_answers {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+int_answers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// a day's solution. `parse` runs once and both parts share what it made.
+pub trait Solution {
+    const YEAR: usize;
+    const DAY: usize;
+
+    type Input;
+
+    fn parse(input: &str) -> Self::Input;
+
+    fn part_1(input: &Self::Input) -> Answer;
+
+    /// the last day of an event only has a part 1
+    fn part_2(_input: &Self::Input) -> Answer {
+        Answer::NotDone
+    }
+}
+
+pub fn solve_part<S: Solution>(parsed: &S::Input, part: u16) -> Answer {
+    match part {
+        1 => S::part_1(parsed),
+        _ => S::part_2(parsed),
+    }
+}
+
+/// parses `input` and runs every part the day has
+pub fn solve<S: Solution>(input: &str) -> Vec<(u16, Answer)> {
+    let parsed = S::parse(input);
+    calendar::parts(S::YEAR, S::DAY)
+        .map(|part| (part, solve_part::<S>(&parsed, part)))
+        .collect()
+}
+
+/// a day's `test_examples`: checks it against every fixture saved by `advent new --fetch` or
+/// `advent examples`, skipping parts that aren't done yet. cargo runs tests from the day's crate.
+pub fn check_examples<S: Solution>() {
+    let dir = env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    check_examples_in::<S>(&dir.join("fixtures"));
+}
+
+/// `check_examples` with the fixtures in `dir`
+pub fn check_examples_in<S: Solution>(dir: &Path) {
+    let fixtures = examples::read_fixtures(dir).unwrap_or_else(|e| panic!("{:#}", e));
+    for fixture in fixtures {
+        let input = S::parse(&fixture.input);
+        for (part, expected) in fixture.answers {
+            let answer = solve_part::<S>(&input, part);
+            if answer.is_done() {
+                assert_eq!(
+                    answer.to_string(),
+                    expected,
+                    "example {} part {}",
+                    fixture.number,
+                    part
+                );
+            }
+        }
+    }
+}
+
+/// the `part_1 => ...` lines every day binary prints, with any drawing below its letters
+pub fn render(answers: &[(u16, Answer)]) -> String {
+    let mut out = String::new();
+    for (part, answer) in answers {
+        out.push_str(&format!("part_{} => {}\n", part, answer));
+        if let Answer::Ocr(drawing) = answer {
+            out.push_str(drawing.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+type Solver = fn(&str) -> Vec<(u16, Answer)>;
+
+/// solutions by year and day, filled in by `register!`
+#[derive(Clone, Default)]
+pub struct Registry {
+    solutions: BTreeMap<(usize, usize), Solver>,
+}
+
+impl Registry {
+    pub fn add<S: Solution>(&mut self) {
+        self.solutions.insert((S::YEAR, S::DAY), solve::<S>);
+    }
+
+    pub fn days(&self) -> Vec<(usize, usize)> {
+        self.solutions.keys().copied().collect()
+    }
+
+    pub fn contains(&self, year: usize, day: usize) -> bool {
+        self.solutions.contains_key(&(year, day))
+    }
+
+    pub fn solve(&self, year: usize, day: usize, input: &str) -> Result<Vec<(u16, Answer)>> {
+        let solver = self.solutions.get(&(year, day)).ok_or_else(|| {
+            Error::msg(format!(
+                "there's no solution registered for {}-{:02}",
+                year, day
+            ))
+        })?;
+        Ok(solver(input))
+    }
+}
+
+/// `register!(Day01, Day02)` defines `registry()`, a `Registry` holding each of them
+#[macro_export]
+macro_rules! register {
+    ($($solution:ty),* $(,)?) => {
+        pub fn registry() -> $crate::puzzle::Registry {
+            let mut registry = $crate::puzzle::Registry::default();
+            $(registry.add::<$solution>();)*
+            registry
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: usize = 2022;
+        const DAY: usize = 1;
+
+        type Input = Vec<u64>;
+
+        fn parse(input: &str) -> Self::Input {
+            input.lines().map(|l| l.parse().unwrap()).collect()
+        }
+
+        fn part_1(input: &Self::Input) -> Answer {
+            input.iter().sum::<u64>().into()
+        }
+
+        fn part_2(input: &Self::Input) -> Answer {
+            input.iter().map(|&n| n as u128).product::<u128>().into()
+        }
+    }
+
+    struct Finale;
+
+    impl Solution for Finale {
+        const YEAR: usize = 2022;
+        const DAY: usize = 25;
+
+        type Input = String;
+
+        fn parse(input: &str) -> Self::Input {
+            input.trim().to_string()
+        }
+
+        fn part_1(input: &Self::Input) -> Answer {
+            input.as_str().into()
+        }
+    }
+
+    crate::register!(Sum, Finale);
+
+    #[rstest]
+    #[case(Answer::from(-3), "-3")]
+    #[case(Answer::from(u128::MAX), "340282366920938463463374607431768211455")]
+    #[case(Answer::from("2=-1=0"), "2=-1=0")]
+    #[case(Answer::ocr(include_str!("../fixtures/ocr/letters.txt")), "EHZRUPCJ")]
+    #[case(Answer::ocr("#..#\n####\n#..#\n"), "#..#/####/#..#")]
+    #[case(Answer::default(), "not done")]
+    fn test_display(#[case] answer: Answer, #[case] expected: &str) {
+        assert_eq!(answer.to_string(), expected);
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+        assert_eq!(registry.days(), vec![(2022, 1), (2022, 25)]);
+
+        let answers = registry.solve(2022, 1, "2\n3\n4\n").unwrap();
+        assert_eq!(render(&answers), "part_1 => 9\npart_2 => 24\n");
+
+        // the last day only has one part
+        let answers = registry.solve(2022, 25, "2=-1=0\n").unwrap();
+        assert_eq!(answers, vec![(1, Answer::from("2=-1=0"))]);
+
+        assert!(registry.solve(2022, 2, "").is_err());
+    }
+
+    #[test]
+    fn test_check_examples() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/examples");
+        // the second example only has a part 1 answer
+        check_examples_in::<Sum>(&fixtures.join("right"));
+    }
+
+    #[test]
+    #[should_panic(expected = "example 1 part 2")]
+    fn test_check_examples_wrong() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/examples");
+        check_examples_in::<Sum>(&fixtures.join("wrong"));
+    }
+
+    #[test]
+    fn test_render_ocr() {
+        let drawing = include_str!("../fixtures/ocr/letters.txt");
+        let rendered = render(&[(1, Answer::ocr(drawing))]);
+        assert_eq!(rendered, format!("part_1 => EHZRUPCJ\n{}", drawing));
+        assert_eq!(
+            crate::examples::parse_answers(&rendered),
+            vec![(1, "EHZRUPCJ".to_string())]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::calendar;
+use crate::config::Config;
+
+// a checkout of this repository is recognized by the day template
+fn is_root(dir: &Path) -> bool {
+    dir.join("template").join("Cargo.toml.tmpl").exists()
+}
+
+pub fn find_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| is_root(dir))
+        .map(Path::to_path_buf)
+}
+
+/// config.toml's `repo_root`, or the checkout we're in
+pub fn root() -> Option<PathBuf> {
+    Config::load()
+        .ok()
+        .and_then(|c| c.repo_root())
+        .or_else(search_root)
+}
+
+/// the checkout the current directory is in
+pub fn search_root() -> Option<PathBuf> {
+    find_root(&env::current_dir().ok()?)
+}
+
+pub fn day_dir(root: &Path, year: usize, day: usize) -> PathBuf {
+    root.join(year.to_string()).join(format!("day{:02}", day))
+}
+
+pub fn fixtures_dir(root: &Path, year: usize, day: usize) -> PathBuf {
+    day_dir(root, year, day).join("fixtures")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Solution {
+    /// no day crate at all
+    Missing,
+    /// the crate is still the template, printing "not done" for both parts
+    NotDone,
+    /// one part still prints "not done"
+    Partial,
+    Done,
+}
+
+// older templates print `part_N => "not done"`, the current one returns `Answer::NotDone`
+// from `fn part_N`, and older days print whatever they like, so a part is done unless
+// its line or its function still says it isn't
+fn part_done(main: &str, part: u16) -> bool {
+    let markers = [format!("part_{}", part), format!("part {}", part)];
+    let mut current = None;
+
+    for line in main.lines().map(str::trim).filter(|l| !l.starts_with("//")) {
+        if let Some(name) = line.split("fn ").nth(1) {
+            current = name.split('(').next().map(str::to_string);
+        }
+        if line.contains("\"not done\"") && markers.iter().any(|m| line.contains(m)) {
+            return false;
+        }
+        if line.contains("Answer::NotDone") && current.as_deref() == Some(markers[0].as_str()) {
+            return false;
+        }
+    }
+
+    true
+}
+
+pub fn solution_state(main: &str, year: usize, day: usize) -> Solution {
+    let done = calendar::parts(year, day)
+        .filter(|&part| part_done(main, part))
+        .count();
+    match (done, calendar::parts(year, day).count()) {
+        (0, _) => Solution::NotDone,
+        (d, parts) if d == parts => Solution::Done,
+        _ => Solution::Partial,
+    }
+}
+
+pub fn solution(root: &Path, year: usize, day: usize) -> Solution {
+    let main = day_dir(root, year, day).join("src").join("main.rs");
+    match fs::read_to_string(main) {
+        Ok(main) => solution_state(&main, year, day),
+        Err(_) => Solution::Missing,
+    }
+}
+
+/// whether the day's `main` hands over to `advent::runner`, which is what reads `--example N`.
+/// older days take no arguments and would run the real input instead
+pub fn uses_runner(root: &Path, year: usize, day: usize) -> bool {
+    let main = day_dir(root, year, day).join("src").join("main.rs");
+    fs::read_to_string(main).is_ok_and(|main| runs_runner(&main))
+}
+
+fn runs_runner(main: &str) -> bool {
+    main.lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with("//"))
+        .any(|l| l.contains("runner::main"))
+}
+
+/// the years with a directory in the repository
+pub fn years(root: &Path) -> Vec<usize> {
+    let mut years: Vec<usize> = fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().to_str()?.parse().ok())
+                .filter(|&year| calendar::days_in(year).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    years.sort_unstable();
+    years
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_find_root() {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let root = find_root(&manifest.join("src")).unwrap();
+        assert_eq!(root, manifest.parent().unwrap());
+        assert_eq!(
+            day_dir(&root, 2022, 1),
+            manifest.parent().unwrap().join("2022").join("day01")
+        );
+
+        // only directories named for an event count, whatever days are checked in
+        let dir = env::temp_dir().join(format!("advent-repo-years-{}", std::process::id()));
+        for sub in ["2022/day01", "2015", "1999", "notes", "template"] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        assert_eq!(years(&dir), vec![2015, 2022]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    const TEMPLATE: &str = include_str!("../../template/plain.rs.tmpl");
+
+    #[rstest]
+    #[case(TEMPLATE, 2022, 1, Solution::NotDone)]
+    #[case(
+        "println!(\"part_1 => {}\", p1);\nprintln!(\"part_2 => {}\", \"not done\");",
+        2022,
+        1,
+        Solution::Partial
+    )]
+    #[case(
+        "println!(\"part_1 => {}\", p1);\n// println!(\"part_2 => {}\", \"not done\");",
+        2022,
+        25,
+        Solution::Done
+    )]
+    #[case(
+        "println!(\"part_1 => {}\", p1);\nprintln!(\"part_2 => {}\", p2);",
+        2022,
+        1,
+        Solution::Done
+    )]
+    #[case(
+        "fn part_1(input: &Input) -> Answer {\n    input.len().into()\n}\n\nfn part_2(input: &Input) -> Answer {\n    Answer::NotDone\n}",
+        2022,
+        1,
+        Solution::Partial
+    )]
+    fn test_solution_state(
+        #[case] main: &str,
+        #[case] year: usize,
+        #[case] day: usize,
+        #[case] expected: Solution,
+    ) {
+        assert_eq!(solution_state(main, year, day), expected);
+    }
+
+    #[rstest]
+    #[case(TEMPLATE, true)]
+    #[case(
+        "use advent::runner;\n\nfn main() {\n    runner::main::<Day>();\n}",
+        true
+    )]
+    #[case(
+        "fn main() {\n    // advent::runner::main::<Day>();\n    println!(\"part_1 => {}\", 1);\n}",
+        false
+    )]
+    #[case(
+        "use advent::input_store;\n\nfn main() {\n    let input = input_store::get_input(2022, 1);\n    println!(\"part_1 => {}\", input.len());\n}",
+        false
+    )]
+    fn test_runs_runner(#[case] main: &str, #[case] expected: bool) {
+        assert_eq!(runs_runner(main), expected);
+    }
+}
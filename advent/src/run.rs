@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{Error, Result};
+use serde::Deserialize;
+
+use crate::examples;
+use crate::input_store;
+use crate::repo;
+
+/// the answers a day printed, skipping the ones that are still "not done"
+pub fn parse_answers(stdout: &str) -> BTreeMap<u16, String> {
+    examples::parse_answers(stdout)
+        .into_iter()
+        .filter(|(_, answer)| !answer.is_empty() && answer != "not done")
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct Message {
+    level: String,
+    rendered: Option<String>,
+}
+
+// a line of cargo's `--message-format=json` output
+#[derive(Deserialize)]
+struct Artifact {
+    reason: String,
+    executable: Option<PathBuf>,
+    message: Option<Message>,
+}
+
+pub fn manifest(root: &Path, year: usize, day: usize) -> Result<PathBuf> {
+    let manifest = repo::day_dir(root, year, day).join("Cargo.toml");
+    if !manifest.exists() {
+        return Err(Error::msg(format!(
+            "there's no crate for {}-{:02} at {}",
+            year,
+            day,
+            manifest.display()
+        )));
+    }
+    Ok(manifest)
+}
+
+/// builds the day's crate, returning the binary. cargo's own output goes to stderr.
+pub fn build(manifest: &Path, release: bool) -> Result<PathBuf> {
+    build_with(manifest, release, false)
+}
+
+/// like `build`, keeping quiet about anything but errors, which end up in the `Err`
+pub fn build_quiet(manifest: &Path, release: bool) -> Result<PathBuf> {
+    build_with(manifest, release, true)
+}
+
+fn build_with(manifest: &Path, release: bool, quiet: bool) -> Result<PathBuf> {
+    let mut cargo = Command::new("cargo");
+    cargo.arg("build").arg("--manifest-path").arg(manifest);
+    if release {
+        cargo.arg("--release");
+    }
+    match quiet {
+        true => cargo.arg("--message-format=json").stderr(Stdio::null()),
+        false => cargo
+            .arg("--message-format=json-render-diagnostics")
+            .stderr(Stdio::inherit()),
+    };
+
+    let output = cargo.output()?;
+    let artifacts: Vec<Artifact> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+
+    if !output.status.success() {
+        let errors: Vec<&str> = artifacts
+            .iter()
+            .filter_map(|a| a.message.as_ref())
+            .filter(|m| m.level == "error")
+            .filter_map(|m| m.rendered.as_deref())
+            .collect();
+        return Err(Error::msg(format!(
+            "cargo build failed for {}\n{}",
+            manifest.display(),
+            errors.join("").trim_end()
+        )));
+    }
+
+    artifacts
+        .into_iter()
+        .filter(|a| a.reason == "compiler-artifact")
+        .filter_map(|a| a.executable)
+        .next_back()
+        .ok_or_else(|| {
+            Error::msg(format!(
+                "cargo didn't build a binary for {}",
+                manifest.display()
+            ))
+        })
+}
+
+pub struct Run {
+    pub stdout: String,
+    pub answers: BTreeMap<u16, String>,
+    /// when each part's answer was printed, counted from the previous one (or the start, for part 1)
+    pub parts: BTreeMap<u16, Duration>,
+    pub elapsed: Duration,
+}
+
+fn run_with(binary: &Path, args: &[&OsStr], stderr: Stdio) -> Result<Run> {
+    let start = Instant::now();
+    let mut child = Command::new(binary)
+        .args(args)
+        .env("AOC_PROFILE", input_store::active_profile())
+        .stdout(Stdio::piped())
+        .stderr(stderr)
+        .spawn()?;
+
+    let mut stdout = String::new();
+    let mut parts = BTreeMap::new();
+    let mut last = start;
+    if let Some(out) = child.stdout.take() {
+        for line in BufReader::new(out).lines() {
+            let line = line?;
+            if let Some((&part, _)) = parse_answers(&line).iter().next() {
+                let now = Instant::now();
+                parts.insert(part, now - last);
+                last = now;
+            }
+            stdout.push_str(&line);
+            stdout.push('\n');
+        }
+    }
+    let status = child.wait()?;
+    let elapsed = start.elapsed();
+
+    if !status.success() {
+        return Err(Error::msg(format!(
+            "{} exited with {}\n{}",
+            binary.display(),
+            status,
+            stdout.trim_end()
+        )));
+    }
+
+    Ok(Run {
+        answers: parse_answers(&stdout),
+        stdout,
+        parts,
+        elapsed,
+    })
+}
+
+/// runs a built day against the active profile's input
+pub fn run(binary: &Path) -> Result<Run> {
+    run_with(binary, &[], Stdio::inherit())
+}
+
+/// like `run`, without whatever the day prints to stderr
+pub fn run_quiet(binary: &Path) -> Result<Run> {
+    run_with(binary, &[], Stdio::null())
+}
+
+/// runs a day built on `advent::runner` against one of its example fixtures, quietly.
+/// check `repo::uses_runner` first, other days ignore `--example` and run the real input
+pub fn run_example(binary: &Path, number: usize) -> Result<Run> {
+    let number = number.to_string();
+    run_with(
+        binary,
+        &["--example".as_ref(), number.as_ref()],
+        Stdio::null(),
+    )
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TestResults {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// adds up the `test result: ok. 3 passed; 0 failed; ...` lines from `cargo test`
+pub fn parse_test_results(output: &str) -> TestResults {
+    let count = |line: &str, what: &str| -> usize {
+        line.split(';')
+            .find_map(|field| field.trim().strip_suffix(what))
+            .and_then(|n| n.trim().rsplit(' ').next()?.parse().ok())
+            .unwrap_or(0)
+    };
+
+    output
+        .lines()
+        .filter(|line| line.starts_with("test result:"))
+        .fold(TestResults::default(), |total, line| TestResults {
+            passed: total.passed + count(line, "passed"),
+            failed: total.failed + count(line, "failed"),
+        })
+}
+
+/// runs the day's tests, `Err` only if cargo couldn't build or run them
+pub fn test(manifest: &Path) -> Result<TestResults> {
+    let output = Command::new("cargo")
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(manifest)
+        .stderr(Stdio::null())
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results = parse_test_results(&stdout);
+
+    if !output.status.success() && results.failed == 0 {
+        return Err(Error::msg(format!(
+            "cargo test failed for {}",
+            manifest.display()
+        )));
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let stdout = [
+            "some debugging",
+            "part_1 => 24000",
+            "part_2 => not done",
+            "part 2 => 45000",
+            "part_3 =>",
+        ]
+        .join("\n");
+
+        let answers = parse_answers(&stdout);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[&1], "24000");
+        assert_eq!(answers[&2], "45000");
+    }
+
+    #[test]
+    fn test_parse_test_results() {
+        let output = [
+            "running 3 tests",
+            "test test::p1_tests::case_1 ... ok",
+            "test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s",
+            "test result: ok. 4 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            parse_test_results(&output),
+            TestResults {
+                passed: 6,
+                failed: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+use anyhow::{Error, Result};
+
+use crate::bench::format_time;
+use crate::calendar;
+use crate::examples;
+use crate::input_store;
+use crate::puzzle::{self, Answer, Registry, Solution};
+use crate::repo;
+
+const USAGE: &str = "options:
+    --part N        only run part N
+    --input PATH    read the input from PATH, `-` for stdin
+    --example N     use the day's fixtures/example_NN.txt
+    --profile NAME  use that profile's cached input
+    --time          show how long parsing and each part took";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// the active profile's cached input
+    Cached,
+    Path(PathBuf),
+    Stdin,
+    Example(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub part: Option<u16>,
+    pub source: Source,
+    pub profile: Option<String>,
+    pub time: bool,
+}
+
+fn value(args: &mut impl Iterator<Item = String>, name: &str) -> Result<String> {
+    args.next()
+        .ok_or_else(|| Error::msg(format!("{} needs a value\n{}", name, USAGE)))
+}
+
+impl Options {
+    /// the options every day binary takes, without the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Options {
+            part: None,
+            source: Source::Cached,
+            profile: None,
+            time: false,
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    let part = value(&mut args, "--part")?;
+                    match part.parse() {
+                        Ok(part @ 1..=2) => options.part = Some(part),
+                        _ => return Err(Error::msg(format!("there's no part {:?}", part))),
+                    }
+                }
+                "--input" | "-i" => {
+                    options.source = match value(&mut args, "--input")?.as_str() {
+                        "-" => Source::Stdin,
+                        path => Source::Path(path.into()),
+                    }
+                }
+                "--example" | "-e" => {
+                    let number = value(&mut args, "--example")?;
+                    let number = number
+                        .parse()
+                        .map_err(|_| Error::msg(format!("{:?} isn't an example number", number)))?;
+                    options.source = Source::Example(number);
+                }
+                "--profile" => options.profile = Some(value(&mut args, "--profile")?),
+                "--time" | "-t" => options.time = true,
+                "--help" | "-h" => return Err(Error::msg(USAGE)),
+                other => return Err(Error::msg(format!("unexpected {:?}\n{}", other, USAGE))),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// the input `options` asks for, with the example's known answers if it's an example
+fn read_input(options: &Options, year: usize, day: usize) -> Result<(String, Vec<(u16, String)>)> {
+    let input = match &options.source {
+        Source::Cached => input_store::try_get_input(year, day)?,
+        Source::Path(path) => input_store::read_file(path.clone())?,
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        }
+        Source::Example(number) => {
+            let root = repo::root()
+                .ok_or_else(|| Error::msg("not in an advent-rs checkout, can't find examples"))?;
+            let fixture = examples::read_fixtures(&repo::fixtures_dir(&root, year, day))?
+                .into_iter()
+                .find(|f| f.number == *number)
+                .ok_or_else(|| {
+                    Error::msg(format!("{}-{:02} has no example {}", year, day, number))
+                })?;
+            return Ok((fixture.input, fixture.answers));
+        }
+    };
+    Ok((input, vec![]))
+}
+
+// checks `options` against the day, and pins the profile when the input comes from the cache.
+// a config.toml that can't be parsed is only warned about, it shouldn't stop a day running
+fn prepare(options: &Options, year: usize, day: usize) -> Result<()> {
+    if let Some(part) = options.part {
+        if !calendar::parts(year, day).contains(&part) {
+            return Err(Error::msg(format!(
+                "{}-{:02} has no part {}, the last day only has one",
+                year, day, part
+            )));
+        }
+    }
+
+    if options.source == Source::Cached {
+        let (_, broken) = input_store::init_profile_lenient(options.profile.as_deref())?;
+        if let Some(err) = broken {
+            eprintln!("warning: ignoring config.toml, {}", err);
+        }
+    }
+    Ok(())
+}
+
+fn solve<S: Solution>(options: &Options) -> Result<()> {
+    prepare(options, S::YEAR, S::DAY)?;
+    let (input, expected) = read_input(options, S::YEAR, S::DAY)?;
+
+    let start = Instant::now();
+    let parsed = S::parse(&input);
+    if options.time {
+        eprintln!("parse took {}", format_time(start.elapsed()));
+    }
+
+    for part in calendar::parts(S::YEAR, S::DAY) {
+        if options.part.is_some_and(|p| p != part) {
+            continue;
+        }
+
+        let start = Instant::now();
+        let answer = puzzle::solve_part::<S>(&parsed, part);
+        let elapsed = start.elapsed();
+        print!("{}", puzzle::render(&[(part, answer.clone())]));
+
+        if options.time {
+            eprintln!("part_{} took {}", part, format_time(elapsed));
+        }
+        if let Some((_, want)) = expected.iter().find(|(p, _)| *p == part) {
+            if answer.is_done() && answer.to_string() != *want {
+                eprintln!("part_{} should be {} for this example", part, want);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn exit_on_error(result: Result<()>) {
+    if let Err(err) = result {
+        eprintln!("error: {:#}", err);
+        process::exit(1);
+    }
+}
+
+/// a whole day binary's `main`: `fn main() { advent::runner::main::<Day>() }`
+pub fn main<S: Solution>() {
+    exit_on_error(Options::parse(std::env::args().skip(1)).and_then(|o| solve::<S>(&o)));
+}
+
+/// `main` for a binary holding many days: `YEAR DAY [options]`, or `--list`
+pub fn main_registry(registry: Registry) {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    exit_on_error(run_registry(&registry, args));
+}
+
+fn run_registry(registry: &Registry, args: Vec<String>) -> Result<()> {
+    if args.first().map(String::as_str) == Some("--list") {
+        for (year, day) in registry.days() {
+            println!("{}-{:02}", year, day);
+        }
+        return Ok(());
+    }
+
+    let number = |arg: Option<&String>, name: &str| -> Result<usize> {
+        arg.and_then(|a| a.parse().ok()).ok_or_else(|| {
+            Error::msg(format!(
+                "expected YEAR DAY [options] or --list, {} missing",
+                name
+            ))
+        })
+    };
+    let year = number(args.first(), "YEAR")?;
+    let day = number(args.get(1), "DAY")?;
+    let options = Options::parse(args.into_iter().skip(2))?;
+
+    calendar::validate(year, day)?;
+    prepare(&options, year, day)?;
+    let (input, _) = read_input(&options, year, day)?;
+    let answers: Vec<(u16, Answer)> = registry
+        .solve(year, day, &input)?
+        .into_iter()
+        .filter(|(part, _)| options.part.is_none_or(|p| p == *part))
+        .collect();
+    print!("{}", puzzle::render(&answers));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[rstest]
+    #[case("", None, Source::Cached, false)]
+    #[case("--part 2 --time", Some(2), Source::Cached, true)]
+    #[case("--input - -p 1", Some(1), Source::Stdin, false)]
+    #[case("--input inputs/a.txt", None, Source::Path("inputs/a.txt".into()), false)]
+    #[case("-e 2 -t", None, Source::Example(2), true)]
+    fn test_options(
+        #[case] line: &str,
+        #[case] part: Option<u16>,
+        #[case] source: Source,
+        #[case] time: bool,
+    ) {
+        let options = Options::parse(args(line)).unwrap();
+        assert_eq!(options.part, part);
+        assert_eq!(options.source, source);
+        assert_eq!(options.time, time);
+    }
+
+    #[test]
+    fn test_prepare() {
+        let (_guard, dir) = input_store::temp_config("runner-prepare");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("config.toml"), "profile = [\n").unwrap();
+
+        let options = Options::parse(args("--part 2")).unwrap();
+        assert!(prepare(&options, 2022, 25).is_err());
+        assert!(prepare(&options, 2022, 24).is_ok());
+
+        input_store::set_profile(Some("pinned")).unwrap();
+        let options = Options::parse(args("--input - --profile alt")).unwrap();
+        prepare(&options, 2022, 1).unwrap();
+        assert_eq!(input_store::active_profile(), "pinned");
+
+        let options = Options::parse(args("--profile alt")).unwrap();
+        prepare(&options, 2022, 1).unwrap();
+        assert_eq!(input_store::active_profile(), "alt");
+
+        // config.toml is broken, so it's the default profile rather than an error
+        prepare(&Options::parse(args("")).unwrap(), 2022, 1).unwrap();
+        assert_eq!(input_store::active_profile(), input_store::DEFAULT_PROFILE);
+
+        input_store::set_profile(None).unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[rstest]
+    #[case("--part 3")]
+    #[case("--part")]
+    #[case("--example one")]
+    #[case("--nope")]
+    fn test_bad_options(#[case] line: &str) {
+        assert!(Options::parse(args(line)).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Error, Result};
+use serde_json::Value;
+
+use crate::input_store;
+use crate::repo;
+
+pub const WORKSPACE: &str = "advent-rs.code-workspace";
+const LINKED_PROJECTS: &str = "rust-analyzer.linkedProjects";
+
+/// the starting points for a new day, each one `template/NAME.rs.tmpl`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Template {
+    Plain,
+    /// a `Space` read from the input's lines
+    Grid,
+    /// instructions run through a `Machine`
+    Vm,
+    /// `a-b` edges and dijkstra over them
+    Graph,
+}
+
+impl Template {
+    pub const ALL: [Template; 4] = [
+        Template::Plain,
+        Template::Grid,
+        Template::Vm,
+        Template::Graph,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Template::Plain => "plain",
+            Template::Grid => "grid",
+            Template::Vm => "vm",
+            Template::Graph => "graph",
+        }
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Template::ALL
+            .iter()
+            .copied()
+            .find(|t| t.name() == s)
+            .ok_or_else(|| Error::msg(format!("there's no {:?} template", s)))
+    }
+}
+
+pub fn template_dir(root: &Path) -> PathBuf {
+    root.join("template")
+}
+
+/// swaps each `{{key}}` for its value, anything else is left alone
+pub fn render(template: &str, vars: &[(&str, String)]) -> String {
+    vars.iter().fold(template.to_string(), |out, (key, value)| {
+        out.replace(&format!("{{{{{}}}}}", key), value)
+    })
+}
+
+pub(crate) fn vars(year: usize, day: usize) -> Vec<(&'static str, String)> {
+    vec![
+        ("year", year.to_string()),
+        ("day", day.to_string()),
+        ("name", format!("day{:02}", day)),
+    ]
+}
+
+/// writes `YEAR/dayDD` from `templates`, refusing to touch a day that's already there
+pub fn new_day(
+    root: &Path,
+    templates: &Path,
+    year: usize,
+    day: usize,
+    template: Template,
+) -> Result<PathBuf> {
+    let dir = repo::day_dir(root, year, day);
+    if dir.exists() {
+        return Err(Error::msg(format!(
+            "{} already exists, not overwriting it",
+            dir.display()
+        )));
+    }
+
+    let vars = vars(year, day);
+    let manifest = render(
+        &input_store::read_file(templates.join("Cargo.toml.tmpl"))?,
+        &vars,
+    );
+    let main = render(
+        &input_store::read_file(templates.join(format!("{}.rs.tmpl", template)))?,
+        &vars,
+    );
+
+    input_store::write_file(dir.join("Cargo.toml"), &manifest)?;
+    input_store::write_file(dir.join("src").join("main.rs"), &main)?;
+
+    Ok(dir)
+}
+
+/// adds `manifest` to the workspace's rust-analyzer `linkedProjects`, false if it was already there
+pub fn link_project(workspace: &Path, manifest: &str) -> Result<bool> {
+    let contents = input_store::read_file(workspace.to_path_buf())?;
+    let mut value: Value = serde_json::from_str(&contents)
+        .map_err(|e| Error::msg(format!("can't read {}: {}", workspace.display(), e)))?;
+
+    let settings = value
+        .as_object_mut()
+        .ok_or_else(|| Error::msg(format!("{} isn't a json object", workspace.display())))?
+        .entry("settings")
+        .or_insert_with(|| Value::Object(Default::default()));
+    let projects = settings
+        .as_object_mut()
+        .ok_or_else(|| Error::msg(format!("{} has odd settings", workspace.display())))?
+        .entry(LINKED_PROJECTS)
+        .or_insert_with(|| Value::Array(vec![]));
+    let projects = projects
+        .as_array_mut()
+        .ok_or_else(|| Error::msg(format!("{} isn't a list", LINKED_PROJECTS)))?;
+
+    if projects.iter().any(|p| p.as_str() == Some(manifest)) {
+        return Ok(false);
+    }
+    projects.push(Value::String(manifest.to_string()));
+
+    input_store::write_file(
+        workspace.to_path_buf(),
+        &(serde_json::to_string_pretty(&value)? + "\n"),
+    )?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    const MANIFEST: &str = include_str!("../../template/Cargo.toml.tmpl");
+    const GRID: &str = include_str!("../../template/grid.rs.tmpl");
+
+    #[test]
+    fn test_render() {
+        let vars = vars(2022, 7);
+        assert!(render(MANIFEST, &vars).contains("name = \"day07\""));
+
+        let main = render(GRID, &vars);
+        assert!(main.contains("const YEAR: usize = 2022;"));
+        assert!(main.contains("const DAY: usize = 7;"));
+        assert!(!main.contains("{{"));
+
+        assert_eq!(render("{{nope}} {{year}}", &vars), "{{nope}} 2022");
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = env::temp_dir().join(format!("advent-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let templates = Path::new(env!("CARGO_MANIFEST_DIR")).join("../template");
+
+        for template in Template::ALL.iter() {
+            let dir = new_day(&root, &templates, 2022, 1 + *template as usize, *template).unwrap();
+            assert!(dir.join("src").join("main.rs").exists());
+        }
+        assert!(new_day(&root, &templates, 2022, 1, Template::Plain).is_err());
+
+        let workspace = root.join(WORKSPACE);
+        fs::write(
+            &workspace,
+            r#"{"folders": [], "settings": {"editor.tabSize": 4}}"#,
+        )
+        .unwrap();
+        assert!(link_project(&workspace, "./2022/day01/Cargo.toml").unwrap());
+        assert!(!link_project(&workspace, "./2022/day01/Cargo.toml").unwrap());
+
+        let value: Value = serde_json::from_str(&fs::read_to_string(&workspace).unwrap()).unwrap();
+        assert_eq!(
+            value["settings"][LINKED_PROJECTS],
+            serde_json::json!(["./2022/day01/Cargo.toml"])
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
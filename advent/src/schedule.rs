@@ -0,0 +1,279 @@
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Error, Result};
+
+use crate::calendar;
+use crate::input_store::Selector;
+use crate::scaffold;
+
+/// where the time comes from, so waiting can be tested without waiting
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// the event that's running at `now`, or the next one if the last is over
+pub fn next_event(now: SystemTime) -> usize {
+    let last = *calendar::event_years_at(now).end();
+    let final_day = *calendar::days(last).end();
+    match calendar::until_unlock(last, final_day, now) {
+        Some(_) => last,
+        None => last + 1,
+    }
+}
+
+/// the days of `year` that haven't unlocked by `now`, soonest first
+pub fn upcoming(year: usize, now: SystemTime) -> Vec<Selector> {
+    Selector::days(year)
+        .filter(|s| calendar::until_unlock(year, s.day(), now).is_some())
+        .collect()
+}
+
+// long waits are slept a minute at a time and the last two minutes a second at a time,
+// so a countdown stays current and a machine waking from sleep doesn't miss the unlock
+fn step(remaining: Duration) -> Duration {
+    match remaining > Duration::from_secs(120) {
+        true => remaining.min(Duration::from_secs(60)),
+        false => remaining.min(Duration::from_secs(1)),
+    }
+}
+
+/// sleeps until `at`, calling `tick` with the time left before each sleep
+pub fn wait_until(clock: &impl Clock, at: SystemTime, mut tick: impl FnMut(Duration)) {
+    while let Ok(remaining) = at.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+        tick(remaining);
+        clock.sleep(step(remaining));
+    }
+}
+
+/// calls `f` until it works, at most `attempts` more times after the first,
+/// sleeping 400ms, 800ms, ... up to `max` in between
+pub fn retry<T>(
+    clock: &impl Clock,
+    attempts: u32,
+    max: Duration,
+    mut f: impl FnMut() -> Result<T>,
+    mut failed: impl FnMut(u32, &Error),
+) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Err(err) if attempt < attempts => {
+                attempt += 1;
+                failed(attempt, &err);
+                clock.sleep(Duration::from_millis(200 * 2u64.pow(attempt)).min(max));
+            }
+            result => return result,
+        }
+    }
+}
+
+/// waits for each of `days` in turn, calling `unlocked` as soon as it has. a day that
+/// fails doesn't stop the rest, its error is returned with it at the end
+pub fn run(
+    clock: &impl Clock,
+    days: &[Selector],
+    mut waiting: impl FnMut(&Selector, Duration),
+    mut unlocked: impl FnMut(&Selector) -> Result<()>,
+) -> Vec<(Selector, Error)> {
+    let mut failures = vec![];
+    for selector in days {
+        let at = calendar::unlocks_at(selector.year(), selector.day());
+        wait_until(clock, at, |remaining| waiting(selector, remaining));
+        if let Err(err) = unlocked(selector) {
+            failures.push((selector.clone(), err));
+        }
+    }
+    failures
+}
+
+/// runs `command` through `sh -c` in `dir`, with `{{year}}`, `{{day}}` and `{{name}}`
+/// filled in and `AOC_YEAR` and `AOC_DAY` set
+pub fn run_hook(command: &str, selector: &Selector, dir: &Path) -> Result<()> {
+    let command = scaffold::render(command, &scaffold::vars(selector.year(), selector.day()));
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .current_dir(dir)
+        .env("AOC_YEAR", selector.year().to_string())
+        .env("AOC_DAY", selector.day().to_string())
+        .status()?;
+
+    if !status.success() {
+        return Err(Error::msg(format!("`{}` exited with {}", command, status)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+    use std::cell::{Cell, RefCell};
+    use std::env;
+    use std::fs;
+
+    struct FakeClock {
+        now: Cell<SystemTime>,
+        slept: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: SystemTime) -> Self {
+            Self {
+                now: Cell::new(now),
+                slept: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.slept.borrow_mut().push(duration);
+        }
+    }
+
+    fn before(year: usize, day: usize, secs: u64) -> SystemTime {
+        calendar::unlocks_at(year, day) - Duration::from_secs(secs)
+    }
+
+    #[rstest]
+    #[case(before(2025, 1, 60 * 60 * 24 * 30), 2025)]
+    #[case(before(2025, 12, 1), 2025)]
+    #[case(calendar::unlocks_at(2025, 12), 2026)]
+    fn test_next_event(#[case] now: SystemTime, #[case] expected: usize) {
+        assert_eq!(next_event(now), expected);
+    }
+
+    #[test]
+    fn test_upcoming() {
+        let days: Vec<usize> = upcoming(2025, calendar::unlocks_at(2025, 10))
+            .iter()
+            .map(|s| s.day())
+            .collect();
+        assert_eq!(days, vec![11, 12]);
+        assert!(upcoming(2024, before(2025, 1, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_wait_until() {
+        let clock = FakeClock::at(before(2025, 1, 185));
+        let mut ticks = 0;
+        wait_until(&clock, calendar::unlocks_at(2025, 1), |_| ticks += 1);
+
+        assert_eq!(clock.now(), calendar::unlocks_at(2025, 1));
+        let slept = clock.slept.borrow();
+        assert_eq!(
+            slept[..2],
+            [Duration::from_secs(60), Duration::from_secs(60)]
+        );
+        assert_eq!(slept.len(), 2 + 65);
+        assert_eq!(ticks, slept.len());
+
+        // already unlocked, no sleeping
+        wait_until(&clock, calendar::unlocks_at(2025, 1), |_| ticks += 1);
+        assert_eq!(ticks, 67);
+    }
+
+    #[test]
+    fn test_run() {
+        let clock = FakeClock::at(before(2025, 11, 30));
+        let days = upcoming(2025, clock.now());
+
+        let mut unlocked = vec![];
+        let failures = run(
+            &clock,
+            &days,
+            |_, _| {},
+            |selector| {
+                assert_eq!(clock.now(), calendar::unlocks_at(2025, selector.day()));
+                unlocked.push(selector.day());
+                match selector.day() {
+                    11 => Err(Error::msg("site is down")),
+                    _ => Ok(()),
+                }
+            },
+        );
+
+        assert_eq!(unlocked, vec![11, 12]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0.day(), 11);
+        assert_eq!(clock.now(), calendar::unlocks_at(2025, 12));
+    }
+
+    #[test]
+    fn test_retry() {
+        let clock = FakeClock::at(before(2025, 1, 0));
+        let mut calls = 0;
+        let result = retry(
+            &clock,
+            3,
+            Duration::from_secs(1),
+            || {
+                calls += 1;
+                match calls {
+                    1..=3 => Err(Error::msg("not yet")),
+                    _ => Ok(calls),
+                }
+            },
+            |_, _| {},
+        );
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(
+            *clock.slept.borrow(),
+            vec![
+                Duration::from_millis(400),
+                Duration::from_millis(800),
+                Duration::from_secs(1)
+            ]
+        );
+
+        let result: Result<()> = retry(
+            &clock,
+            2,
+            Duration::from_secs(1),
+            || Err(Error::msg("never")),
+            |_, _| {},
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_run_hook() {
+        let dir = env::temp_dir().join(format!("advent-hook-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let selector = Selector::new(2022, 7).unwrap();
+
+        run_hook("echo {{name}} $AOC_YEAR > out.txt", &selector, &dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("out.txt")).unwrap(),
+            "day07 2022\n"
+        );
+        assert!(run_hook("exit 3", &selector, &dir).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+use crate::error::{advent_error, AdventError};
+use crate::html::{self, Node};
+
+/// who the site thinks we are, from the header on any page
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct User {
+    pub name: String,
+    pub stars: Option<u32>,
+    pub supporter: bool,
+}
+
+impl Display for User {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.supporter {
+            write!(f, " (AoC++)")?;
+        }
+        if let Some(stars) = self.stars {
+            write!(f, ", {} stars", stars)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn logged_out(err: &anyhow::Error) -> bool {
+    matches!(advent_error(err), Some(AdventError::LoggedOut))
+}
+
+// inputs are plain text, so only an html page can carry the login link
+fn is_html(body: &str) -> bool {
+    let start = body.trim_start().to_ascii_lowercase();
+    start.starts_with("<!doctype") || start.starts_with("<html") || start.starts_with("<header")
+}
+
+/// whether a response body is the site telling us to log in
+pub fn is_logged_out(body: &str) -> bool {
+    if body.contains("Please log in") {
+        return true;
+    }
+    is_html(body) && body.contains("/auth/login") && user(body).is_none()
+}
+
+pub fn user(page: &str) -> Option<User> {
+    let nodes = html::parse(page);
+    let div = html::find_where(&nodes, |n| n.is("div") && n.has_class("user"))
+        .into_iter()
+        .next()?;
+
+    let name: String = div
+        .children()
+        .iter()
+        .filter_map(|c| match c {
+            Node::Text(t) => Some(t.as_str()),
+            _ => None,
+        })
+        .collect();
+    let name = html::decode_entities(name.trim());
+    if name.is_empty() {
+        return None;
+    }
+
+    let stars = html::find_where(div.children(), |n| n.has_class("star-count"))
+        .first()
+        .and_then(|n| n.text().trim().trim_end_matches('*').parse().ok());
+    let supporter =
+        !html::find_where(div.children(), |n| n.has_class("supporter-badge")).is_empty();
+
+    Some(User {
+        name,
+        stars,
+        supporter,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    const LOGGED_IN: &str = include_str!("../fixtures/session/logged_in.html");
+    const LOGGED_OUT: &str = include_str!("../fixtures/session/logged_out.html");
+    const ANONYMOUS: &str = include_str!("../fixtures/session/anonymous.html");
+
+    #[test]
+    fn test_user() {
+        assert_eq!(
+            user(LOGGED_IN),
+            Some(User {
+                name: "kryptn".to_string(),
+                stars: Some(50),
+                supporter: true,
+            })
+        );
+        assert_eq!(
+            user(ANONYMOUS).unwrap().name,
+            "(anonymous user #1234567)".to_string()
+        );
+        assert_eq!(user(LOGGED_OUT), None);
+    }
+
+    #[rstest]
+    #[case(LOGGED_IN, false)]
+    #[case(ANONYMOUS, false)]
+    #[case(LOGGED_OUT, true)]
+    #[case(
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        true
+    )]
+    #[case("1\n2\n3\n", false)]
+    // an input that happens to mention the path isn't a login page
+    #[case("/auth/login\n", false)]
+    fn test_is_logged_out(#[case] body: &str, #[case] expected: bool) {
+        assert_eq!(is_logged_out(body), expected);
+    }
+}
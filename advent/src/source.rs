@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Error, Result};
+
+use crate::calendar;
+use crate::input_store::{self, Selector};
+use crate::integrity::{self, Problem};
+
+/// somewhere puzzle inputs come from
+pub trait InputSource {
+    fn fetch_input(&self, selector: &Selector) -> Result<String>;
+}
+
+impl<S: InputSource + ?Sized> InputSource for Box<S> {
+    fn fetch_input(&self, selector: &Selector) -> Result<String> {
+        (**self).fetch_input(selector)
+    }
+}
+
+impl<S: InputSource + ?Sized> InputSource for &S {
+    fn fetch_input(&self, selector: &Selector) -> Result<String> {
+        (**self).fetch_input(selector)
+    }
+}
+
+/// a directory laid out like the input cache, `root/YEAR/DD/input`
+#[derive(Clone, Debug)]
+pub struct DirectorySource {
+    pub root: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl InputSource for DirectorySource {
+    fn fetch_input(&self, selector: &Selector) -> Result<String> {
+        let mut path = self.root.clone();
+        path.push(selector.year().to_string());
+        path.push(format!("{:02}", selector.day()));
+        path.push("input");
+
+        fs::read_to_string(&path).map_err(|e| {
+            Error::msg(format!(
+                "no input for {}-{:02} at {}: {}",
+                selector.year(),
+                selector.day(),
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MemorySource {
+    pub inputs: HashMap<(usize, usize), String>,
+}
+
+impl MemorySource {
+    pub fn with(mut self, year: usize, day: usize, input: &str) -> Self {
+        self.inputs.insert((year, day), input.to_string());
+        self
+    }
+}
+
+impl InputSource for MemorySource {
+    fn fetch_input(&self, selector: &Selector) -> Result<String> {
+        self.inputs
+            .get(&(selector.year(), selector.day()))
+            .cloned()
+            .ok_or_else(|| {
+                Error::msg(format!(
+                    "no input for {}-{:02}",
+                    selector.year(),
+                    selector.day()
+                ))
+            })
+    }
+}
+
+pub struct Refetched {
+    pub value: String,
+    /// where the previous copy was kept and how it differs, if the input changed
+    pub changed: Option<(PathBuf, String)>,
+}
+
+/// reads through the local input cache, only asking `inner` for inputs that aren't cached yet
+pub struct Cached<S> {
+    pub inner: S,
+}
+
+impl<S: InputSource> Cached<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+
+    /// the cached input, unless it's missing or too broken to use (empty, truncated, an html page)
+    pub fn get(&self, selector: &Selector, force: bool) -> Result<String> {
+        if !force && selector.exists() {
+            let problems = input_store::check_puzzle_input(selector)?;
+            if !problems.iter().any(Problem::is_fatal) {
+                return input_store::read_puzzle_input(selector);
+            }
+        }
+
+        Ok(self.refetch(selector)?.value)
+    }
+
+    /// fetches the input even if it's cached, keeping the old copy around if it changed
+    pub fn refetch(&self, selector: &Selector) -> Result<Refetched> {
+        let previous = match selector.exists() {
+            true => Some(input_store::read_puzzle_input(selector)?),
+            false => None,
+        };
+
+        let value = self.inner.fetch_input(selector)?;
+        let archived = input_store::replace_puzzle_input(selector, value.clone())?;
+
+        let changed = match (archived, previous) {
+            (Some(path), Some(previous)) => Some((path, integrity::diff(&previous, &value))),
+            _ => None,
+        };
+
+        Ok(Refetched { value, changed })
+    }
+
+    pub fn get_all(&self, year: usize, force: bool) -> Result<()> {
+        for selector in Selector::days(year) {
+            self.get(&selector, force)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: InputSource> InputSource for Cached<S> {
+    fn fetch_input(&self, selector: &Selector) -> Result<String> {
+        self.get(selector, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_memory_source() {
+        let source = MemorySource::default().with(2022, 1, "1\n2\n");
+        assert_eq!(
+            source
+                .fetch_input(&Selector::new(2022, 1).unwrap())
+                .unwrap(),
+            "1\n2\n"
+        );
+        assert!(source
+            .fetch_input(&Selector::new(2022, 2).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_directory_source_and_cache() {
+        let (_guard, tmp) = input_store::temp_config("source");
+        let mirror = tmp.join("mirror");
+        fs::create_dir_all(mirror.join("2022").join("01")).unwrap();
+        fs::write(mirror.join("2022").join("01").join("input"), "mirrored").unwrap();
+
+        let cached = Cached::new(DirectorySource::new(&mirror));
+        let selector = Selector::new(2022, 1).unwrap();
+
+        assert!(!selector.exists());
+        assert_eq!(cached.get(&selector, false).unwrap(), "mirrored");
+        assert!(selector.exists());
+
+        // the cache answers from now on, even if the mirror disappears
+        fs::remove_dir_all(&mirror).unwrap();
+        assert_eq!(cached.fetch_input(&selector).unwrap(), "mirrored");
+        assert!(cached.get(&selector, true).is_err());
+
+        // a broken cache entry is fetched again
+        let cached = Cached::new(MemorySource::default().with(2022, 1, "fixed\n"));
+        input_store::write_puzzle_input(&selector, "".to_string()).unwrap();
+        assert_eq!(cached.get(&selector, false).unwrap(), "fixed\n");
+
+        let changed = Cached::new(MemorySource::default().with(2022, 1, "changed\n"))
+            .refetch(&selector)
+            .unwrap()
+            .changed
+            .unwrap();
+        assert!(changed.1.contains("-fixed\n+changed\n"));
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+}
@@ -124,11 +124,10 @@ pub trait Traversal<T: Point> {
         let results: Vec<FinalPath<T>> = directions
             .iter()
             .cloned()
-            .map(|d| {
+            .flat_map(|d| {
                 let ctx = ctx.clone() + d;
                 self.traverse(ctx)
             })
-            .flatten()
             .collect();
 
         if results.is_empty() {
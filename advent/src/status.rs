@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::calendar;
+use crate::html;
+use crate::repo::Solution;
+
+/// stars per day from an event's calendar page, `/YEAR`
+pub fn parse_stars(page: &str) -> BTreeMap<usize, u8> {
+    let nodes = html::parse(page);
+    let mut stars = BTreeMap::new();
+
+    for link in html::find_all(&nodes, "a") {
+        let day = link
+            .attr("class")
+            .into_iter()
+            .flat_map(str::split_whitespace)
+            .find_map(|c| c.strip_prefix("calendar-day")?.parse::<usize>().ok());
+        let day = match day {
+            Some(day) => day,
+            None => continue,
+        };
+
+        let count = if link.has_class("calendar-verycomplete") {
+            2
+        } else if link.has_class("calendar-complete") {
+            1
+        } else {
+            0
+        };
+        stars.insert(day, count);
+    }
+
+    stars
+}
+
+pub struct YearStatus {
+    pub year: usize,
+    /// `None` when the site couldn't be asked
+    pub stars: Option<BTreeMap<usize, u8>>,
+    pub solutions: BTreeMap<usize, Solution>,
+}
+
+impl YearStatus {
+    pub(crate) fn stars_on(&self, day: usize) -> Option<u8> {
+        self.stars
+            .as_ref()
+            .map(|s| s.get(&day).copied().unwrap_or(0))
+    }
+
+    pub(crate) fn solution(&self, day: usize) -> Solution {
+        self.solutions
+            .get(&day)
+            .copied()
+            .unwrap_or(Solution::Missing)
+    }
+
+    /// days with stars on the site but no finished solution here
+    pub fn gaps(&self) -> Vec<usize> {
+        calendar::days(self.year)
+            .filter(|&d| self.stars_on(d).unwrap_or(0) > 0 && self.solution(d) != Solution::Done)
+            .collect()
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let days = calendar::days(self.year);
+
+        let tens: String = days
+            .clone()
+            .map(|d| match d / 10 {
+                0 => ' ',
+                n => char::from_digit(n as u32, 10).unwrap(),
+            })
+            .collect();
+        let ones: String = days
+            .clone()
+            .map(|d| char::from_digit((d % 10) as u32, 10).unwrap())
+            .collect();
+        writeln!(out, "{:<6} {}", self.year, tens.trim_end()).unwrap();
+        writeln!(out, "{:<6} {}", "", ones).unwrap();
+
+        let stars: String = days
+            .clone()
+            .map(|d| match self.stars_on(d) {
+                Some(2) => '★',
+                Some(1) => '☆',
+                Some(_) => '·',
+                None => '?',
+            })
+            .collect();
+        writeln!(out, "{:<6} {}", "stars", stars).unwrap();
+
+        let repo: String = days
+            .clone()
+            .map(|d| match self.solution(d) {
+                Solution::Done => '✓',
+                Solution::Partial => '½',
+                Solution::NotDone => '○',
+                Solution::Missing => '·',
+            })
+            .collect();
+        writeln!(out, "{:<6} {}", "repo", repo).unwrap();
+
+        let total_stars: u32 = days
+            .clone()
+            .filter_map(|d| self.stars_on(d))
+            .map(u32::from)
+            .sum();
+        let done = days
+            .clone()
+            .filter(|&d| self.solution(d) == Solution::Done)
+            .count();
+        let crates = days
+            .clone()
+            .filter(|&d| self.solution(d) != Solution::Missing)
+            .count();
+        let stars = match self.stars {
+            Some(_) => format!("{} stars", total_stars),
+            None => "stars unknown".to_string(),
+        };
+        write!(
+            out,
+            "{:<6} {}, {} day crates, {} solved",
+            "", stars, crates, done
+        )
+        .unwrap();
+
+        let gaps = self.gaps();
+        if !gaps.is_empty() {
+            let gaps: Vec<String> = gaps.iter().map(usize::to_string).collect();
+            write!(out, ", not solved here: {}", gaps.join(" ")).unwrap();
+        }
+        writeln!(out).unwrap();
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CALENDAR: &str = include_str!("../fixtures/calendar/2022.html");
+
+    #[test]
+    fn test_parse_stars() {
+        let stars = parse_stars(CALENDAR);
+        let expected: BTreeMap<usize, u8> =
+            vec![(1, 2), (2, 2), (3, 1), (4, 0)].into_iter().collect();
+        assert_eq!(stars, expected);
+    }
+
+    #[test]
+    fn test_render() {
+        let status = YearStatus {
+            year: 2025,
+            stars: Some(parse_stars(CALENDAR)),
+            solutions: vec![
+                (1, Solution::Done),
+                (2, Solution::Partial),
+                (4, Solution::NotDone),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(status.gaps(), vec![2, 3]);
+
+        let expected = [
+            "2025            111",
+            "       123456789012",
+            "stars  ★★☆·········",
+            "repo   ✓½·○········",
+            "       5 stars, 3 day crates, 1 solved, not solved here: 2 3",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(status.render(), expected);
+    }
+}
@@ -0,0 +1,17 @@
+// just_numbers drops minus signs. a `-` is a sign unless it follows a letter or digit,
+// so `x=-3` is -3 and `1-3` is 1 and 3
+fn signed_numbers(s: &str) -> Vec<isize> {
+    let mut numbers = vec![];
+    let mut number = String::new();
+    let mut last = ' ';
+    for c in s.chars().chain([' ']) {
+        if c.is_ascii_digit() || (c == '-' && !last.is_alphanumeric()) {
+            number.push(c);
+        } else if !number.is_empty() {
+            numbers.extend(number.parse::<isize>().ok());
+            number.clear();
+        }
+        last = c;
+    }
+    numbers
+}
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::calendar;
+use crate::cooldown;
+use crate::error::{advent_error, AdventError};
+use crate::fetch::Site;
+use crate::input_store::{self, Selector};
+use crate::source::{Cached, InputSource};
+
+/// `2015..2025`, `2015..=2025` or a single `2022`. both ends are included either way.
+pub fn parse_years(value: &str) -> Result<RangeInclusive<usize>> {
+    let bad = || Error::msg(format!("expected YEAR or YEAR..YEAR, got {:?}", value));
+
+    let (start, end) = match value.split_once("..") {
+        Some((start, end)) => (start, end.trim_start_matches('=')),
+        None => (value, value),
+    };
+    let start: usize = start.trim().parse().map_err(|_| bad())?;
+    let end: usize = end.trim().parse().map_err(|_| bad())?;
+    if start > end {
+        return Err(bad());
+    }
+
+    Ok(start..=end)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayProgress {
+    pub input: bool,
+    pub puzzle: bool,
+    pub error: Option<String>,
+}
+
+/// what previous syncs got through, keyed by `YEAR-DD`, so an interrupted sync picks up where it left off
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Progress {
+    pub days: BTreeMap<String, DayProgress>,
+}
+
+fn key(selector: &Selector) -> String {
+    format!("{}-{:02}", selector.year(), selector.day())
+}
+
+impl Progress {
+    pub fn load() -> Result<Self> {
+        let path = input_store::sync_progress_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&input_store::read_file(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = input_store::sync_progress_path()?;
+        input_store::write_file(path, &serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, selector: &Selector) -> DayProgress {
+        self.days.get(&key(selector)).cloned().unwrap_or_default()
+    }
+
+    fn set(&mut self, selector: &Selector, day: DayProgress) {
+        self.days.insert(key(selector), day);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Fetched,
+    Cached,
+    Locked,
+    Failed(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Fetched => write!(f, "fetched"),
+            Outcome::Cached => write!(f, "already cached"),
+            Outcome::Locked => write!(f, "not unlocked yet"),
+            Outcome::Failed(reason) => write!(f, "failed: {}", reason),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    pub fetched: Vec<Selector>,
+    pub cached: Vec<Selector>,
+    pub locked: Vec<Selector>,
+    pub failed: Vec<(Selector, String)>,
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} fetched, {} already cached, {} not unlocked yet, {} failed",
+            self.fetched.len(),
+            self.cached.len(),
+            self.locked.len(),
+            self.failed.len()
+        )
+    }
+}
+
+// errors that will fail every day after this one too, so there's no point carrying on
+fn is_fatal(err: &Error) -> bool {
+    if cooldown::throttled(err).is_some() {
+        return true;
+    }
+    matches!(
+        advent_error(err),
+        Some(
+            AdventError::LoggedOut
+                | AdventError::MissingCookie { .. }
+                | AdventError::InvalidCookie
+                | AdventError::MissingConfig
+        )
+    )
+}
+
+pub struct Sync<'a, S> {
+    pub inputs: &'a Cached<S>,
+    /// fetches puzzle descriptions too, if set
+    pub puzzles: Option<&'a Site>,
+    /// how long to wait after each request to the site
+    pub throttle: Duration,
+}
+
+impl<'a, S: InputSource> Sync<'a, S> {
+    // waits after every request, including failed ones. not after errors that stop the sync,
+    // there's no next request to wait for
+    fn request<T>(&self, request: impl FnOnce() -> Result<T>) -> Result<T> {
+        let result = request();
+        if !matches!(&result, Err(err) if is_fatal(err)) {
+            sleep(self.throttle);
+        }
+        result
+    }
+
+    fn day(&self, selector: &Selector, progress: &mut DayProgress) -> Result<Outcome> {
+        if calendar::check_unlocked(selector.year(), selector.day()).is_err() {
+            return Ok(Outcome::Locked);
+        }
+
+        let mut fetched = false;
+
+        if !progress.input || !selector.exists() {
+            match selector.exists() {
+                true => self.inputs.get(selector, false)?,
+                false => {
+                    fetched = true;
+                    self.request(|| self.inputs.get(selector, false))?
+                }
+            };
+            progress.input = true;
+        }
+
+        if let Some(site) = self.puzzles {
+            if !progress.puzzle || !selector.puzzle_filename()?.exists() {
+                match selector.puzzle_filename()?.exists() {
+                    true => site.get_or_fetch_puzzle(selector, false)?,
+                    false => {
+                        fetched = true;
+                        self.request(|| site.get_or_fetch_puzzle(selector, false))?
+                    }
+                };
+                progress.puzzle = true;
+            }
+        }
+
+        Ok(match fetched {
+            true => Outcome::Fetched,
+            false => Outcome::Cached,
+        })
+    }
+
+    /// fetches everything missing for `years`, calling `report` as each day finishes.
+    /// progress is saved after every day, and a failed day doesn't stop the rest.
+    pub fn run(
+        &self,
+        years: RangeInclusive<usize>,
+        mut report: impl FnMut(&Selector, &Outcome),
+    ) -> Result<Summary> {
+        let mut progress = Progress::load()?;
+        let mut summary = Summary::default();
+
+        for year in years {
+            for selector in Selector::days(year) {
+                let mut day_progress = progress.get(&selector);
+
+                let outcome = match self.day(&selector, &mut day_progress) {
+                    Ok(outcome) => {
+                        day_progress.error = None;
+                        outcome
+                    }
+                    Err(err) if is_fatal(&err) => {
+                        progress.save()?;
+                        return Err(err);
+                    }
+                    Err(err) => {
+                        let reason = format!("{:#}", err);
+                        day_progress.error = Some(reason.clone());
+                        Outcome::Failed(reason)
+                    }
+                };
+
+                progress.set(&selector, day_progress);
+                progress.save()?;
+                report(&selector, &outcome);
+
+                match outcome {
+                    Outcome::Fetched => summary.fetched.push(selector),
+                    Outcome::Cached => summary.cached.push(selector),
+                    Outcome::Locked => summary.locked.push(selector),
+                    Outcome::Failed(reason) => summary.failed.push((selector, reason)),
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::source::MemorySource;
+    use rstest::*;
+
+    #[rstest]
+    #[case("2015..2025", Some(2015..=2025))]
+    #[case("2015..=2025", Some(2015..=2025))]
+    #[case("2022", Some(2022..=2022))]
+    #[case("2025..2015", None)]
+    #[case("twenty", None)]
+    fn test_parse_years(#[case] value: &str, #[case] expected: Option<RangeInclusive<usize>>) {
+        assert_eq!(parse_years(value).ok(), expected);
+    }
+
+    #[test]
+    fn test_sync() {
+        const DAYS: usize = 25;
+        let (_guard, dir) = input_store::temp_config("sync");
+
+        let mut source = MemorySource::default();
+        for day in 1..=DAYS {
+            if day != 7 {
+                source = source.with(2015, day, "1\n");
+            }
+        }
+        input_store::write_puzzle_input(&Selector::new(2015, 2).unwrap(), "cached\n".to_string())
+            .unwrap();
+
+        let inputs = Cached::new(source);
+        let sync = Sync {
+            inputs: &inputs,
+            puzzles: None,
+            throttle: Duration::ZERO,
+        };
+
+        let mut reported = 0;
+        let summary = sync.run(2015..=2015, |_, _| reported += 1).unwrap();
+        assert_eq!(reported, DAYS);
+        assert_eq!(summary.fetched.len(), DAYS - 2);
+        assert_eq!(summary.cached.len(), 1);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].0.day(), 7);
+
+        // everything but the failed day is skipped on the next run
+        let progress = Progress::load().unwrap();
+        assert!(progress.get(&Selector::new(2015, 1).unwrap()).input);
+        assert!(progress
+            .get(&Selector::new(2015, 7).unwrap())
+            .error
+            .is_some());
+
+        let summary = sync.run(2015..=2015, |_, _| {}).unwrap();
+        assert_eq!(summary.fetched.len(), 0);
+        assert_eq!(summary.cached.len(), DAYS - 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_sync_waits_after_failures() {
+        let (_guard, dir) = input_store::temp_config("sync-failures");
+
+        let inputs = Cached::new(MemorySource::default().with(2015, 1, "1\n"));
+        let sync = Sync {
+            inputs: &inputs,
+            puzzles: None,
+            throttle: Duration::from_millis(20),
+        };
+
+        let start = std::time::Instant::now();
+        let summary = sync.run(2015..=2015, |_, _| {}).unwrap();
+        assert_eq!(summary.failed.len(), 24);
+        assert!(start.elapsed() >= Duration::from_millis(20 * 25));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::calendar;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Right,
+    Wrong {
+        expected: String,
+        got: String,
+    },
+    /// the day didn't print an answer for this part
+    Missing {
+        expected: String,
+    },
+    /// there's no known answer to compare with
+    Unknown,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Right => write!(f, "ok"),
+            Check::Wrong { expected, got } => write!(f, "wrong, got {} expected {}", got, expected),
+            Check::Missing { expected } => write!(f, "no answer, expected {}", expected),
+            Check::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// what a day printed against the answers known to be right
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayCheck {
+    pub year: usize,
+    pub day: usize,
+    pub parts: BTreeMap<u16, Check>,
+}
+
+impl DayCheck {
+    pub fn new(
+        year: usize,
+        day: usize,
+        known: &BTreeMap<u16, String>,
+        printed: &BTreeMap<u16, String>,
+    ) -> Self {
+        let parts = calendar::parts(year, day)
+            .map(|part| {
+                let check = match (known.get(&part), printed.get(&part)) {
+                    (None, _) => Check::Unknown,
+                    (Some(expected), None) => Check::Missing {
+                        expected: expected.clone(),
+                    },
+                    (Some(expected), Some(got)) if expected == got => Check::Right,
+                    (Some(expected), Some(got)) => Check::Wrong {
+                        expected: expected.clone(),
+                        got: got.clone(),
+                    },
+                };
+                (part, check)
+            })
+            .collect();
+
+        Self { year, day, parts }
+    }
+
+    /// nothing wrong or missing, unknown parts don't count against it
+    pub fn passed(&self) -> bool {
+        self.parts
+            .values()
+            .all(|c| matches!(c, Check::Right | Check::Unknown))
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.parts.values().all(|c| *c == Check::Unknown)
+    }
+}
+
+impl Display for DayCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .parts
+            .iter()
+            .map(|(part, check)| format!("part {} {}", part, check))
+            .collect();
+        write!(f, "{}-{:02} {}", self.year, self.day, parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn answers(pairs: &[(u16, &str)]) -> BTreeMap<u16, String> {
+        pairs.iter().map(|(p, a)| (*p, a.to_string())).collect()
+    }
+
+    #[test]
+    fn test_day_check() {
+        let known = answers(&[(1, "7"), (2, "5")]);
+
+        let check = DayCheck::new(2022, 1, &known, &answers(&[(1, "7"), (2, "5")]));
+        assert!(check.passed());
+        assert_eq!(check.to_string(), "2022-01 part 1 ok, part 2 ok");
+
+        let check = DayCheck::new(2022, 1, &known, &answers(&[(1, "8")]));
+        assert!(!check.passed());
+        assert_eq!(
+            check.to_string(),
+            "2022-01 part 1 wrong, got 8 expected 7, part 2 no answer, expected 5"
+        );
+
+        let check = DayCheck::new(2022, 1, &answers(&[]), &answers(&[(1, "8")]));
+        assert!(check.passed());
+        assert!(check.is_unknown());
+
+        // the last day only has part 1
+        let check = DayCheck::new(2022, 25, &answers(&[(1, "x")]), &answers(&[(1, "x")]));
+        assert_eq!(check.parts.len(), 1);
+    }
+}
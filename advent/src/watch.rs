@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::bench::format_time;
+use crate::run::TestResults;
+
+/// modification time and length of every file below some directories
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, (SystemTime, u64)>,
+}
+
+fn walk(dir: &Path, files: &mut BTreeMap<PathBuf, (SystemTime, u64)>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let meta = match entry.metadata() {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+        if meta.is_dir() {
+            walk(&path, files);
+        } else if let Ok(modified) = meta.modified() {
+            files.insert(path, (modified, meta.len()));
+        }
+    }
+}
+
+impl Snapshot {
+    /// directories that don't exist (yet) are just empty
+    pub fn take(dirs: &[PathBuf]) -> Self {
+        let mut files = BTreeMap::new();
+        for dir in dirs {
+            walk(dir, &mut files);
+        }
+        Self { files }
+    }
+
+    /// files added, removed or modified since `before`
+    pub fn changed(&self, before: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, stamp)| before.files.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            before
+                .files
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+/// how one run went, against an example or the real input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Answers {
+        answers: BTreeMap<u16, String>,
+        /// what an example's answers should be, empty for the real input
+        expected: Vec<(u16, String)>,
+        elapsed: Duration,
+    },
+    Failed(String),
+    /// not run, and not a failure either
+    Skipped(String),
+}
+
+/// everything `advent watch` shows after a rebuild
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Panel {
+    pub title: String,
+    pub build: Result<(), String>,
+    pub tests: Option<Result<TestResults, String>>,
+    /// by fixture number, 0 for anything about the examples as a whole
+    pub examples: Vec<(usize, Outcome)>,
+    pub input: Option<Outcome>,
+}
+
+fn first_line(reason: &str) -> &str {
+    reason.lines().next().unwrap_or_default()
+}
+
+fn outcome(out: &mut String, label: &str, outcome: &Outcome) {
+    match outcome {
+        Outcome::Failed(reason) => {
+            writeln!(out, "{:<10} ✗ {}", label, first_line(reason)).unwrap();
+        }
+        Outcome::Skipped(reason) => writeln!(out, "{:<10} - {}", label, reason).unwrap(),
+        Outcome::Answers {
+            answers,
+            expected,
+            elapsed,
+        } => {
+            let mut parts = vec![];
+            for (part, answer) in answers {
+                let mark = match expected.iter().find(|(p, _)| p == part) {
+                    Some((_, want)) if want == answer => " ✓".to_string(),
+                    Some((_, want)) => format!(" ✗ expected {}", want),
+                    None => String::new(),
+                };
+                parts.push(format!("part_{} => {}{}", part, answer, mark));
+            }
+            if parts.is_empty() {
+                parts.push("no answers yet".to_string());
+            }
+            writeln!(
+                out,
+                "{:<10} {}  ({})",
+                label,
+                parts.join("   "),
+                format_time(*elapsed)
+            )
+            .unwrap();
+        }
+    }
+}
+
+impl Panel {
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{}", self.title).unwrap();
+
+        match &self.build {
+            Ok(()) => writeln!(out, "{:<10} ✓", "build").unwrap(),
+            Err(reason) => {
+                writeln!(out, "{:<10} ✗", "build").unwrap();
+                writeln!(out, "{}", reason.trim_end()).unwrap();
+            }
+        }
+
+        match &self.tests {
+            Some(Ok(results)) if results.failed == 0 => {
+                writeln!(out, "{:<10} ✓ {} passed", "tests", results.passed).unwrap()
+            }
+            Some(Ok(results)) => writeln!(
+                out,
+                "{:<10} ✗ {} failed, {} passed",
+                "tests", results.failed, results.passed
+            )
+            .unwrap(),
+            Some(Err(reason)) => writeln!(out, "{:<10} ✗ {}", "tests", first_line(reason)).unwrap(),
+            None => {}
+        }
+
+        for (number, result) in &self.examples {
+            let label = match number {
+                0 => "examples".to_string(),
+                n => format!("example {}", n),
+            };
+            outcome(&mut out, &label, result);
+        }
+        if let Some(result) = &self.input {
+            outcome(&mut out, "input", result);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = env::temp_dir().join(format!("advent-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src").join("main.rs"), "fn main() {}").unwrap();
+
+        let dirs = vec![dir.join("src"), dir.join("fixtures")];
+        let before = Snapshot::take(&dirs);
+        assert!(Snapshot::take(&dirs).changed(&before).is_empty());
+
+        fs::write(dir.join("src").join("main.rs"), "fn main() { }").unwrap();
+        fs::create_dir_all(dir.join("fixtures")).unwrap();
+        fs::write(dir.join("fixtures").join("example_01.txt"), "1").unwrap();
+        let after = Snapshot::take(&dirs);
+        assert_eq!(
+            after.changed(&before),
+            vec![
+                dir.join("fixtures").join("example_01.txt"),
+                dir.join("src").join("main.rs"),
+            ]
+        );
+
+        fs::remove_file(dir.join("fixtures").join("example_01.txt")).unwrap();
+        assert_eq!(Snapshot::take(&dirs).changed(&after).len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_render() {
+        let answers = |pairs: &[(u16, &str)]| -> BTreeMap<u16, String> {
+            pairs.iter().map(|(p, a)| (*p, a.to_string())).collect()
+        };
+        let panel = Panel {
+            title: "2022-07".to_string(),
+            build: Ok(()),
+            tests: Some(Ok(TestResults {
+                passed: 2,
+                failed: 1,
+            })),
+            examples: vec![
+                (
+                    1,
+                    Outcome::Answers {
+                        answers: answers(&[(1, "95437"), (2, "12")]),
+                        expected: vec![(1, "95437".to_string()), (2, "13".to_string())],
+                        elapsed: Duration::from_micros(120),
+                    },
+                ),
+                (2, Outcome::Failed("panicked\nat main.rs".to_string())),
+            ],
+            input: Some(Outcome::Answers {
+                answers: answers(&[]),
+                expected: vec![],
+                elapsed: Duration::from_millis(3),
+            }),
+        };
+
+        let expected = [
+            "2022-07",
+            "build      ✓",
+            "tests      ✗ 1 failed, 2 passed",
+            "example 1  part_1 => 95437 ✓   part_2 => 12 ✗ expected 13  (120µs)",
+            "example 2  ✗ panicked",
+            "input      no answers yet  (3.00ms)",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(panel.render(), expected);
+    }
+
+    #[test]
+    fn test_render_skipped() {
+        let panel = Panel {
+            title: "2017-01".to_string(),
+            build: Ok(()),
+            tests: None,
+            examples: vec![(
+                0,
+                Outcome::Skipped(
+                    "skipped, the day doesn't use advent::runner to read them".to_string(),
+                ),
+            )],
+            input: None,
+        };
+        assert_eq!(
+            panel.render(),
+            "2017-01\nbuild      ✓\nexamples   - skipped, the day doesn't use advent::runner to read them\n"
+        );
+    }
+}
@@ -1,34 +0,0 @@
-use advent::input_store;
-
-const YEAR: usize = 0;
-const DAY: usize = 0;
-
-fn main() {
-    let input = input_store::get_input(YEAR, DAY);
-
-    println!("part_1 => {}", "not done");
-    println!("part_2 => {}", "not done");
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use rstest::*;
-
-    #[test]
-    fn do_test() {
-        assert_eq!(2, 2);
-    }
-
-    #[rstest]
-    #[case("ADVENT", "ADVENT")]
-    fn p1_tests(#[case] given: &str, #[case] expected: &str) {
-        assert_eq!(given, expected);
-    }
-
-    #[rstest]
-    #[case("ADVENT", "ADVENT")]
-    fn p2_tests(#[case] given: &str, #[case] expected: &str) {
-        assert_eq!(given, expected);
-    }
-}